@@ -0,0 +1,197 @@
+use aoc_common::{load_input, Error, Skipped};
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use tracing::info_span;
+
+use crate::days::{self, Answer};
+
+/// How running one day turned out
+pub enum Outcome {
+    Solved {
+        answers: Vec<Answer>,
+        skipped: Skipped,
+    },
+    Unsolved,
+    Failed(Error),
+    Panicked(String),
+}
+
+/// Outcome of running both parts of one day, with the wall time it took
+pub struct DayReport {
+    pub day: u8,
+    pub elapsed: Duration,
+    pub outcome: Outcome,
+}
+
+/// Message carried by a panic, if it is a string
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(s) => *s,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(s) => s.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// Run `solve` for each day on a pool of `jobs` threads, returning a report
+/// per day sorted by day. A day which fails or panics doesn't stop the rest.
+pub fn run_parallel<F>(days: &[u8], jobs: usize, strict: bool, solve: F) -> Vec<DayReport>
+where
+    F: Fn(u8, &mut Skipped) -> Result<Vec<Answer>, Error> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(Vec::with_capacity(days.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            scope.spawn(|| {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let _span = info_span!("day", day).entered();
+                    let start = Instant::now();
+                    let mut skipped = Skipped::new(strict);
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| solve(day, &mut skipped)));
+
+                    let outcome = match result {
+                        Ok(Ok(answers)) => Outcome::Solved { answers, skipped },
+                        Ok(Err(Error::Unsolved { .. })) => Outcome::Unsolved,
+                        Ok(Err(e)) => Outcome::Failed(e),
+                        Err(payload) => Outcome::Panicked(panic_message(payload)),
+                    };
+
+                    let report = DayReport {
+                        day,
+                        elapsed: start.elapsed(),
+                        outcome,
+                    };
+                    reports.lock().unwrap().push(report);
+                }
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort_by_key(|r| r.day);
+
+    reports
+}
+
+/// Shorten an answer to fit in a table cell, leaving multi-line answers
+/// (e.g. images) to be printed after the table
+fn cell(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        n => format!("({} lines)", n),
+    }
+}
+
+fn print_table(reports: &[DayReport]) {
+    println!(
+        "{:>3}  {:<8} {:>10}  {:<16} Part 2",
+        "Day", "Status", "Time", "Part 1"
+    );
+    for r in reports {
+        let (status, detail) = match &r.outcome {
+            Outcome::Solved { answers, .. } => {
+                let cells: Vec<String> = answers.iter().map(|a| cell(&a.answer)).collect();
+                ("ok", format!("{:<16} {}", cells[0], cells[1]))
+            }
+            Outcome::Unsolved => ("unsolved", String::new()),
+            Outcome::Failed(e) => ("error", e.to_string()),
+            Outcome::Panicked(msg) => ("panic", msg.clone()),
+        };
+
+        let row = format!(
+            "{:>3}  {:<8} {:>10}  {}",
+            r.day,
+            status,
+            format!("{:.1?}", r.elapsed),
+            detail
+        );
+        println!("{}", row.trim_end());
+    }
+}
+
+/// Run both parts of every given day against the input at `input_path(day)`,
+/// `jobs` days at a time, then print a summary table. Returns whether no day
+/// failed.
+pub fn run<P>(days: &[u8], jobs: usize, strict: bool, input_path: P) -> Result<bool, Error>
+where
+    P: Fn(u8) -> Result<PathBuf, Error> + Sync,
+{
+    let reports = run_parallel(days, jobs, strict, |day, skipped| {
+        let path = input_path(day)?;
+        let input = load_input(&path.to_string_lossy())?;
+
+        days::run(day, &input, &[1, 2], skipped)
+    });
+
+    print_table(&reports);
+
+    for r in &reports {
+        let Outcome::Solved { answers, skipped } = &r.outcome else {
+            continue;
+        };
+
+        for a in answers.iter().filter(|a| a.answer.contains('\n')) {
+            println!("\nDay {} part {}:\n{}", r.day, a.part, a.answer);
+        }
+        if !skipped.is_empty() {
+            eprint!("Day {}: {}", r.day, skipped);
+        }
+    }
+
+    Ok(reports
+        .iter()
+        .all(|r| matches!(r.outcome, Outcome::Solved { .. } | Outcome::Unsolved)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(part: u8, answer: &str) -> Answer {
+        Answer {
+            part,
+            answer: answer.to_string(),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_run_parallel_isolates_failures() {
+        let reports = run_parallel(&[4, 3, 2, 1], 3, true, |day, _| match day {
+            2 => panic!("day {} exploded", day),
+            3 => Err(Error::Unsolved { day }),
+            4 => Err(Error::Io(std::io::ErrorKind::NotFound.into())),
+            _ => Ok(vec![answer(1, "24000"), answer(2, "45000")]),
+        });
+
+        let days: Vec<u8> = reports.iter().map(|r| r.day).collect();
+        assert_eq!(days, [1, 2, 3, 4]);
+
+        assert!(
+            matches!(&reports[0].outcome, Outcome::Solved { answers, .. } if answers[1].answer == "45000")
+        );
+        assert!(matches!(&reports[1].outcome, Outcome::Panicked(msg) if msg == "day 2 exploded"));
+        assert!(matches!(reports[2].outcome, Outcome::Unsolved));
+        assert!(matches!(reports[3].outcome, Outcome::Failed(_)));
+    }
+
+    #[test]
+    fn test_cell() {
+        assert_eq!(cell("24000"), "24000");
+        assert_eq!(cell("#.\n.#"), "(2 lines)");
+    }
+}
@@ -0,0 +1,255 @@
+use aoc_common::{load_input, read_input, Error, Skipped};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::days::{self, day_dir};
+
+/// Summary statistics for repeated runs of one stage (parsing or a part) of a day
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Timing {
+    pub day: u8,
+    pub stage: String,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+/// Timings for every benchmarked day, as saved to and loaded from JSON
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Report {
+    pub iterations: usize,
+    pub timings: Vec<Timing>,
+}
+
+/// A stage whose median time grew beyond the allowed threshold
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub stage: String,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Regression {
+    pub fn percent_change(&self) -> f64 {
+        100.0 * (self.current_ns as f64 - self.baseline_ns as f64) / self.baseline_ns as f64
+    }
+}
+
+/// Value at the given percentile (0-100) of sorted samples, by nearest rank
+fn percentile(sorted: &[u64], pct: usize) -> u64 {
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn summarize(day: u8, stage: &str, samples: &mut [u64]) -> Timing {
+    samples.sort_unstable();
+
+    Timing {
+        day,
+        stage: stage.to_string(),
+        min_ns: samples[0],
+        median_ns: percentile(samples, 50),
+        p95_ns: percentile(samples, 95),
+    }
+}
+
+/// Time parsing and both parts of a day, running each `iterations` times
+pub fn bench_day(day: u8, input: &str, iterations: usize) -> Result<Vec<Timing>, Error> {
+    let mut parse = Vec::with_capacity(iterations);
+    let mut parts = [
+        Vec::with_capacity(iterations),
+        Vec::with_capacity(iterations),
+    ];
+
+    for _ in 0..iterations.max(1) {
+        for answer in days::run(day, input, &[1, 2], &mut Skipped::new(true))? {
+            if answer.part == 1 {
+                parse.push(answer.parse_time.as_nanos() as u64);
+            }
+            parts[usize::from(answer.part) - 1].push(answer.solve_time.as_nanos() as u64);
+        }
+    }
+
+    Ok(vec![
+        summarize(day, "parse", &mut parse),
+        summarize(day, "part1", &mut parts[0]),
+        summarize(day, "part2", &mut parts[1]),
+    ])
+}
+
+/// Find stages whose median time grew by more than `threshold` percent
+pub fn compare(baseline: &[Timing], current: &[Timing], threshold: f64) -> Vec<Regression> {
+    current
+        .iter()
+        .filter_map(|t| {
+            let base = baseline
+                .iter()
+                .find(|b| b.day == t.day && b.stage == t.stage)?;
+            let limit = base.median_ns as f64 * (1.0 + threshold / 100.0);
+
+            (t.median_ns as f64 > limit).then(|| Regression {
+                day: t.day,
+                stage: t.stage.clone(),
+                baseline_ns: base.median_ns,
+                current_ns: t.median_ns,
+            })
+        })
+        .collect()
+}
+
+fn fmt_ns(ns: u64) -> String {
+    format!("{:.1?}", Duration::from_nanos(ns))
+}
+
+fn print_table(timings: &[Timing]) {
+    println!(
+        "{:>3}  {:<6} {:>10} {:>10} {:>10}",
+        "Day", "Stage", "Min", "Median", "P95"
+    );
+    for t in timings {
+        println!(
+            "{:>3}  {:<6} {:>10} {:>10} {:>10}",
+            t.day,
+            t.stage,
+            fmt_ns(t.min_ns),
+            fmt_ns(t.median_ns),
+            fmt_ns(t.p95_ns)
+        );
+    }
+}
+
+/// Options for `aoc bench`
+pub struct Options<'a> {
+    pub iterations: usize,
+    pub json: bool,
+    pub save: Option<&'a Path>,
+    pub baseline: Option<&'a Path>,
+    pub threshold: f64,
+}
+
+/// Benchmark each day against its `input.txt`, or the input from the store
+/// given by `stored`, then report and compare timings. Returns whether no
+/// stage regressed beyond the threshold.
+pub fn run(
+    root: &Path,
+    days: &[u8],
+    opts: &Options,
+    stored: impl Fn(u8) -> Result<Option<PathBuf>, Error>,
+) -> Result<bool, Error> {
+    let mut timings = Vec::new();
+
+    for &day in days {
+        let timed = stored(day)
+            .map(|stored| stored.unwrap_or_else(|| day_dir(root, day).join("input.txt")))
+            .and_then(|path| load_input(&path.to_string_lossy()))
+            .and_then(|input| bench_day(day, &input, opts.iterations));
+
+        match timed {
+            Ok(t) => timings.extend(t),
+            Err(e) => eprintln!("Day {}: skipped ({})", day, e),
+        }
+    }
+
+    let report = Report {
+        iterations: opts.iterations,
+        timings,
+    };
+    let json = serde_json::to_string_pretty(&report).expect("timings serialize to JSON");
+
+    if opts.json {
+        println!("{}", json);
+    } else {
+        print_table(&report.timings);
+    }
+
+    if let Some(path) = opts.save {
+        fs::write(path, &json).map_err(|source| Error::Open {
+            path: path.display().to_string(),
+            source,
+        })?;
+    }
+
+    let Some(path) = opts.baseline else {
+        return Ok(true);
+    };
+    let baseline: Report =
+        serde_json::from_str(&read_input(&path.to_string_lossy())?).map_err(|e| Error::Open {
+            path: path.display().to_string(),
+            source: e.into(),
+        })?;
+
+    let regressions = compare(&baseline.timings, &report.timings, opts.threshold);
+    for r in &regressions {
+        eprintln!(
+            "Day {} {}: median {} vs baseline {} ({:+.1}%)",
+            r.day,
+            r.stage,
+            fmt_ns(r.current_ns),
+            fmt_ns(r.baseline_ns),
+            r.percent_change()
+        );
+    }
+    eprintln!(
+        "{} stage(s) slower than baseline by more than {}%",
+        regressions.len(),
+        opts.threshold
+    );
+
+    Ok(regressions.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u8, stage: &str, median_ns: u64) -> Timing {
+        Timing {
+            day,
+            stage: stage.to_string(),
+            min_ns: median_ns,
+            median_ns,
+            p95_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<u64> = (1..=20).collect();
+
+        assert_eq!(percentile(&samples, 0), 1);
+        assert_eq!(percentile(&samples, 50), 10);
+        assert_eq!(percentile(&samples, 95), 19);
+        assert_eq!(percentile(&samples, 100), 20);
+        assert_eq!(percentile(&[7], 95), 7);
+    }
+
+    #[test]
+    fn test_summarize() {
+        let mut samples = vec![5, 1, 4, 2, 3];
+        let t = summarize(1, "parse", &mut samples);
+
+        assert_eq!(t.min_ns, 1);
+        assert_eq!(t.median_ns, 3);
+        assert_eq!(t.p95_ns, 5);
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = vec![timing(1, "part1", 100), timing(1, "part2", 100)];
+        let current = vec![
+            timing(1, "part1", 109),
+            timing(1, "part2", 150),
+            timing(2, "part1", 500),
+        ];
+
+        let regressions = compare(&baseline, &current, 10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].stage, "part2");
+        assert_eq!(regressions[0].percent_change(), 50.0);
+    }
+}
@@ -0,0 +1,239 @@
+use aoc_common::{Error, Skipped, Solution, Streaming};
+use std::{
+    io::BufRead,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use tracing::debug_span;
+
+/// Days with a solution registered in the runner
+pub const DAYS: [u8; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+/// Directory holding a day's crate and input files
+pub fn day_dir(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("day{:02}", day))
+}
+
+/// The answer to one part of a puzzle, with the time spent producing it
+pub struct Answer {
+    pub part: u8,
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Solve the given parts of a day's puzzle, recording any malformed lines
+/// skipped while parsing
+pub fn run(
+    day: u8,
+    input: &str,
+    parts: &[u8],
+    skipped: &mut Skipped,
+) -> Result<Vec<Answer>, Error> {
+    match day {
+        1 => run_solution::<day1::Day1>(input, parts, skipped),
+        2 => run_solution::<day2::Day2>(input, parts, skipped),
+        3 => run_solution::<day3::Day3>(input, parts, skipped),
+        4 => run_solution::<day4::Day4>(input, parts, skipped),
+        5 => run_solution::<day5::Day5>(input, parts, skipped),
+        6 => run_solution::<day6::Day6>(input, parts, skipped),
+        7 => run_solution::<day7::Day7>(input, parts, skipped),
+        8 => run_solution::<day8::Day8>(input, parts, skipped),
+        9 => run_solution::<day9::Day9>(input, parts, skipped),
+        10 => run_solution::<day10::Day10>(input, parts, skipped),
+        _ => Err(Error::Unsolved { day }),
+    }
+}
+
+/// Solve both parts of a day's puzzle in a single pass over a reader,
+/// without loading the whole input into memory
+pub fn stream<R: BufRead>(day: u8, reader: R, skipped: &mut Skipped) -> Result<[String; 2], Error> {
+    let _span = debug_span!("stream").entered();
+
+    match day {
+        1 => day1::Day1::stream(reader, skipped),
+        2 => day2::Day2::stream(reader, skipped),
+        3 => day3::Day3::stream(reader, skipped),
+        4 => day4::Day4::stream(reader, skipped),
+        6 => day6::Day6::stream(reader, skipped),
+        9 => day9::Day9::stream(reader, skipped),
+        10 => day10::Day10::stream(reader, skipped),
+        _ if DAYS.contains(&day) => Err(Error::Unsupported {
+            day,
+            feature: "streaming",
+        }),
+        _ => Err(Error::Unsolved { day }),
+    }
+}
+
+/// Parse an input once, then solve each requested part from it
+fn run_solution<S: Solution>(
+    input: &str,
+    parts: &[u8],
+    skipped: &mut Skipped,
+) -> Result<Vec<Answer>, Error> {
+    let start = Instant::now();
+    let parsed = debug_span!("parse").in_scope(|| S::parse(input, skipped))?;
+    let parse_time = start.elapsed();
+
+    parts
+        .iter()
+        .map(|&part| {
+            let _span = debug_span!("solve", part).entered();
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1(&parsed)?.to_string(),
+                _ => S::part2(&parsed)?.to_string(),
+            };
+
+            Ok(Answer {
+                part,
+                answer,
+                parse_time,
+                solve_time: start.elapsed(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen;
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    /// Days which can be streamed in memory that does not grow with the input.
+    /// Day 9 also streams, but remembers every position its tails visit,
+    /// which grows with the length of the motions.
+    const STREAMING_DAYS: [u8; 6] = [1, 2, 3, 4, 6, 10];
+
+    // Count bytes allocated by each thread, so tests running in parallel
+    // do not disturb each other's measurements
+    thread_local! {
+        static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+        static PEAK: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct CountingAlloc;
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                let _ = ALLOCATED.try_with(|allocated| {
+                    let now = allocated.get() + layout.size();
+                    allocated.set(now);
+                    PEAK.with(|peak| peak.set(peak.get().max(now)));
+                });
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            let _ = ALLOCATED
+                .try_with(|allocated| allocated.set(allocated.get().saturating_sub(layout.size())));
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+    /// Peak number of bytes allocated by this thread while running `f`,
+    /// beyond what was already allocated
+    fn peak_memory<F: FnOnce()>(f: F) -> usize {
+        let before = ALLOCATED.with(|allocated| allocated.get());
+        PEAK.with(|peak| peak.set(before));
+        f();
+
+        PEAK.with(|peak| peak.get()) - before
+    }
+
+    fn generated(day: u8, size: usize) -> Vec<u8> {
+        let mut input = Vec::new();
+        gen::generate(day, size, 0, &mut input).unwrap();
+        input
+    }
+
+    #[test]
+    fn test_stream_matches_run() {
+        for day in STREAMING_DAYS.into_iter().chain([9]) {
+            let input = generated(day, 1000);
+            let text = std::str::from_utf8(&input).unwrap();
+
+            let answers = run(day, text, &[1, 2], &mut Skipped::new(true)).unwrap();
+            let streamed = stream(day, input.as_slice(), &mut Skipped::new(true)).unwrap();
+            assert_eq!(streamed[0], answers[0].answer, "day {} part 1", day);
+            assert_eq!(streamed[1], answers[1].answer, "day {} part 2", day);
+        }
+    }
+
+    #[test]
+    fn test_stream_memory_is_flat() {
+        for day in STREAMING_DAYS {
+            let small = generated(day, 10_000);
+            let large = generated(day, 100_000);
+
+            let small_peak = peak_memory(|| {
+                stream(day, small.as_slice(), &mut Skipped::new(true)).unwrap();
+            });
+            let large_peak = peak_memory(|| {
+                stream(day, large.as_slice(), &mut Skipped::new(true)).unwrap();
+            });
+
+            assert!(
+                large_peak <= small_peak + 1024,
+                "day {}: {} bytes for {} bytes of input, {} bytes for {}",
+                day,
+                small_peak,
+                small.len(),
+                large_peak,
+                large.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_unsupported_day() {
+        assert!(matches!(
+            stream(5, "".as_bytes(), &mut Skipped::new(true)),
+            Err(Error::Unsupported { day: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_run_example() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+        let answers = run(4, input, &[1, 2], &mut Skipped::new(true)).unwrap();
+
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].part, 1);
+        assert_eq!(answers[0].answer, "2");
+        assert_eq!(answers[1].part, 2);
+        assert_eq!(answers[1].answer, "4");
+    }
+
+    #[test]
+    fn test_run_skips_malformed_lines() {
+        let input = "2-4,6-8\n2-3,4-x\n2-8,3-7\n";
+        let mut skipped = Skipped::new(false);
+        let answers = run(4, input, &[1], &mut skipped).unwrap();
+
+        assert_eq!(answers[0].answer, "1");
+        assert_eq!(skipped.errors().len(), 1);
+        assert_eq!(skipped.errors()[0].line, 2);
+
+        assert!(run(4, input, &[1], &mut Skipped::new(true)).is_err());
+    }
+
+    #[test]
+    fn test_run_unknown_day() {
+        assert!(matches!(
+            run(25, "", &[1], &mut Skipped::new(true)),
+            Err(Error::Unsolved { day: 25 })
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+use aoc_common::Error;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::io::Write;
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// Write a random, valid puzzle input for a day.
+///
+/// `size` sets the number of records (elves, rounds, moves, ...), or the
+/// side of the grid for day 8 and the length of the buffer for day 6. The same
+/// seed always produces the same input.
+pub fn generate<W: Write>(day: u8, size: usize, seed: u64, out: &mut W) -> Result<(), Error> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    match day {
+        1 => calories(&mut rng, size, out),
+        2 => strategy_guide(&mut rng, size, out),
+        3 => rucksacks(&mut rng, size, out),
+        4 => section_pairs(&mut rng, size, out),
+        5 => crane_procedure(&mut rng, size, out),
+        6 => signal(&mut rng, size, out),
+        7 => terminal(&mut rng, size, out),
+        8 => tree_grid(&mut rng, size, out),
+        9 => motions(&mut rng, size, out),
+        10 => program(&mut rng, size, out),
+        _ => return Err(Error::Unsolved { day }),
+    }?;
+
+    Ok(out.flush()?)
+}
+
+/// Inventories of `n` elves, separated by blank lines
+fn calories<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    for i in 0..n {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        for _ in 0..rng.gen_range(1..=15) {
+            writeln!(out, "{}", rng.gen_range(1000..=60000))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `n` rounds of rock paper scissors
+fn strategy_guide<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    for _ in 0..n {
+        let opponent = ['A', 'B', 'C'][rng.gen_range(0..3)];
+        let response = ['X', 'Y', 'Z'][rng.gen_range(0..3)];
+        writeln!(out, "{} {}", opponent, response)?;
+    }
+
+    Ok(())
+}
+
+/// At least `n` rucksacks, in groups of three elves sharing a single badge.
+///
+/// Each elf draws its items from its own third of the alphabet, so the badge
+/// is the only item common to a group. Each elf's third is split again
+/// between its compartments, so only the one item packed in both is shared.
+fn rucksacks<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    let mut items: Vec<u8> = (b'a'..=b'z').chain(b'A'..=b'Z').collect();
+
+    for _ in 0..n.div_ceil(3) {
+        items.shuffle(rng);
+        let (badge, rest) = items.split_first().unwrap();
+
+        for pool in rest.chunks(rest.len() / 3) {
+            let (shared, pool) = pool.split_first().unwrap();
+            let (first_pool, second_pool) = pool.split_at(pool.len() / 2);
+            let extra = rng.gen_range(0..=first_pool.len());
+
+            let mut first = vec![*badge, *shared];
+            first.extend((0..extra).map(|_| *first_pool.choose(rng).unwrap()));
+            let mut second = vec![*shared];
+            second.extend((0..=extra).map(|_| *second_pool.choose(rng).unwrap()));
+            first.shuffle(rng);
+            second.shuffle(rng);
+
+            first.append(&mut second);
+            out.write_all(&first)?;
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `n` pairs of section ranges
+fn section_pairs<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    for _ in 0..n {
+        let mut range = || {
+            let start = rng.gen_range(1..=99);
+            (start, rng.gen_range(start..=99))
+        };
+        let (a, b) = range();
+        let (c, d) = range();
+        writeln!(out, "{}-{},{}-{}", a, b, c, d)?;
+    }
+
+    Ok(())
+}
+
+/// A diagram of nine stacks of crates, followed by `n` moves which never take
+/// more crates from a stack than it holds
+fn crane_procedure<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    const N_STACKS: usize = 9;
+    let mut heights: Vec<usize> = (0..N_STACKS).map(|_| rng.gen_range(1..=8)).collect();
+
+    let top = *heights.iter().max().unwrap();
+    for level in (0..top).rev() {
+        let row: Vec<String> = heights
+            .iter()
+            .map(|&h| match h > level {
+                true => format!("[{}]", rng.gen_range(b'A'..=b'Z') as char),
+                false => "   ".to_string(),
+            })
+            .collect();
+        writeln!(out, "{}", row.join(" ").trim_end())?;
+    }
+    let labels: Vec<String> = (1..=N_STACKS).map(|i| format!(" {} ", i)).collect();
+    writeln!(out, "{}\n", labels.join(" "))?;
+
+    for _ in 0..n {
+        let nonempty: Vec<usize> = (0..N_STACKS).filter(|&i| heights[i] > 0).collect();
+        let src = *nonempty.choose(rng).unwrap();
+        let dst = (src + rng.gen_range(1..N_STACKS)) % N_STACKS;
+        let count = rng.gen_range(1..=heights[src].min(10));
+
+        heights[src] -= count;
+        heights[dst] += count;
+        writeln!(out, "move {} from {} to {}", count, src + 1, dst + 1)?;
+    }
+
+    Ok(())
+}
+
+/// A buffer of `n` characters (at least 20) with a start-of-message marker
+/// planted at 90% of its length.
+///
+/// Every character before the marker repeats one of the three before it, and
+/// the marker starts by repeating the character before it, so neither kind of
+/// marker can appear any earlier.
+fn signal<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    let n = n.max(20);
+    let planted = (n * 9 / 10).clamp(3, n - 14);
+
+    let mut buffer = vec![LOWERCASE[rng.gen_range(0..26)]; 3];
+    while buffer.len() < planted {
+        let recent = &buffer[buffer.len() - 3..];
+        buffer.push(recent[rng.gen_range(0..3)]);
+    }
+
+    let last = buffer[planted - 1];
+    let mut marker: Vec<u8> = LOWERCASE.iter().copied().filter(|&c| c != last).collect();
+    marker.shuffle(rng);
+    buffer.push(last);
+    buffer.extend(&marker[..13]);
+
+    buffer.extend((buffer.len()..n).map(|_| LOWERCASE[rng.gen_range(0..26)]));
+    out.write_all(&buffer)?;
+    writeln!(out)?;
+
+    Ok(())
+}
+
+/// A terminal session exploring a filesystem of `n` directories
+fn terminal<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    // Build the tree first: each directory's parent is one created before it
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); n.max(1)];
+    for dir in 1..children.len() {
+        let parent = rng.gen_range(0..dir);
+        children[parent].push(dir);
+    }
+
+    writeln!(out, "$ cd /")?;
+
+    // Depth-first walk, listing each directory before entering its children
+    let mut stack = vec![Some(0)];
+    while let Some(entry) = stack.pop() {
+        let Some(dir) = entry else {
+            writeln!(out, "$ cd ..")?;
+            continue;
+        };
+
+        if dir != 0 {
+            writeln!(out, "$ cd d{}", dir)?;
+            stack.push(None);
+        }
+        writeln!(out, "$ ls")?;
+        for child in &children[dir] {
+            writeln!(out, "dir d{}", child)?;
+        }
+        for i in 0..rng.gen_range(0..5) {
+            let name = match ["txt", "dat", "log", ""][rng.gen_range(0..4)] {
+                "" => format!("f{}", i),
+                ext => format!("f{}.{}", i, ext),
+            };
+            writeln!(out, "{} {}", rng.gen_range(1..300_000), name)?;
+        }
+        stack.extend(children[dir].iter().rev().map(|&child| Some(child)));
+    }
+
+    Ok(())
+}
+
+/// An `n` by `n` grid of tree heights
+fn tree_grid<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    for _ in 0..n {
+        let row: Vec<u8> = (0..n).map(|_| rng.gen_range(b'0'..=b'9')).collect();
+        out.write_all(&row)?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// `n` motions of the head of the rope
+fn motions<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    for _ in 0..n {
+        let direction = ['U', 'D', 'L', 'R'][rng.gen_range(0..4)];
+        writeln!(out, "{} {}", direction, rng.gen_range(1..=20))?;
+    }
+
+    Ok(())
+}
+
+/// A program of `n` instructions
+fn program<W: Write>(rng: &mut ChaCha8Rng, n: usize, out: &mut W) -> Result<(), Error> {
+    for _ in 0..n {
+        if rng.gen_bool(0.3) {
+            writeln!(out, "noop")?;
+        } else {
+            writeln!(out, "addx {}", rng.gen_range(-20..=20))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use aoc_common::Skipped;
+
+    fn generated(day: u8, size: usize, seed: u64) -> String {
+        let mut out = Vec::new();
+        generate(day, size, seed, &mut out).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_generated_inputs_parse() {
+        // Day 7 does not parse its input yet
+        for day in days::DAYS.into_iter().filter(|&day| day != 7) {
+            for seed in 0..5 {
+                let input = generated(day, 300, seed);
+                let answers = days::run(day, &input, &[1, 2], &mut Skipped::new(true));
+                assert!(answers.is_ok(), "day {} seed {}", day, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        assert_eq!(generated(5, 50, 7), generated(5, 50, 7));
+        assert_ne!(generated(5, 50, 7), generated(5, 50, 8));
+    }
+
+    #[test]
+    fn test_generated_rucksacks() {
+        let input = generated(3, 10, 1);
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 12);
+
+        for line in lines {
+            let (first, second) = line.split_at(line.len() / 2);
+            let shared: Vec<char> = first.chars().filter(|&c| second.contains(c)).collect();
+            assert!(shared.windows(2).all(|w| w[0] == w[1]), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_generated_signal() {
+        let input = generated(6, 1000, 3);
+        let buffer = input.trim_end();
+
+        assert_eq!(buffer.len(), 1000);
+        assert_eq!(day6::find_marker(buffer, 4), 904);
+        assert_eq!(day6::find_marker(buffer, 14), 914);
+    }
+
+    #[test]
+    fn test_generated_terminal() {
+        let input = generated(7, 100, 2);
+        let cds = input.lines().filter(|l| l.starts_with("$ cd d")).count();
+        let ups = input.lines().filter(|l| *l == "$ cd ..").count();
+
+        assert_eq!(cds, 99);
+        assert_eq!(ups, 99);
+        for line in input.lines().filter(|l| !l.starts_with('$')) {
+            assert!(day7::parse_node(line, None).is_ok(), "{}", line);
+        }
+    }
+}
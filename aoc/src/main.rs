@@ -0,0 +1,673 @@
+use aoc_common::{init_tracing, load_input, open, Error, Output, Skipped};
+use clap::{ArgAction, Args, Parser, Subcommand};
+use serde::Serialize;
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+mod all;
+mod bench;
+mod days;
+mod gen;
+mod scaffold;
+mod store;
+mod verify;
+mod watch;
+
+/// Directory containing the `dayNN` folders, when running from a checkout
+const WORKSPACE_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+#[derive(Parser, Debug)]
+#[command(name = "aoc", about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[arg(
+        short = 'v',
+        long = "verbose",
+        help = "Trace solver internals (-v for debug, -vv for trace)",
+        action = ArgAction::Count,
+        global = true
+    )]
+    verbose: u8,
+
+    #[arg(
+        long = "trace",
+        help = "Write trace events as JSON to this file",
+        global = true
+    )]
+    trace: Option<PathBuf>,
+}
+
+/// Where to find stored puzzle inputs, and whose to use
+#[derive(Args, Debug)]
+struct StoreArgs {
+    #[arg(
+        long = "store",
+        env = "AOC_STORE",
+        help = "Directory of stored inputs",
+        default_value_os_t = store::default_root()
+    )]
+    store: PathBuf,
+
+    #[arg(
+        long = "year",
+        env = "AOC_YEAR",
+        help = "Calendar year of the puzzle",
+        default_value_t = store::DEFAULT_YEAR
+    )]
+    year: u16,
+
+    #[arg(
+        long = "user",
+        env = "AOC_USER",
+        help = "Account whose input to use",
+        value_parser = store::parse_user,
+        default_value = store::DEFAULT_USER
+    )]
+    user: String,
+}
+
+impl StoreArgs {
+    fn key(&self, day: u8) -> store::Key {
+        store::Key {
+            year: self.year,
+            user: self.user.clone(),
+            day,
+        }
+    }
+
+    /// Find the stored input for a day
+    fn resolve(&self, day: u8) -> Result<PathBuf, Error> {
+        store::Store::new(&self.store).resolve(&self.key(day))
+    }
+}
+
+/// A stored input to use in place of each day's `input.txt`, chosen by giving
+/// a year or user
+#[derive(Args, Debug)]
+struct StoredInputArgs {
+    #[arg(
+        long = "store",
+        env = "AOC_STORE",
+        help = "Directory of stored inputs",
+        default_value_os_t = store::default_root()
+    )]
+    store: PathBuf,
+
+    #[arg(
+        long = "year",
+        help = "Use the stored input for this calendar year instead of input.txt"
+    )]
+    year: Option<u16>,
+
+    #[arg(
+        long = "user",
+        help = "Use this account's stored input instead of input.txt",
+        value_parser = store::parse_user
+    )]
+    user: Option<String>,
+}
+
+impl StoredInputArgs {
+    /// Find the stored input for a day, if a year or user was given
+    fn resolve(&self, day: u8) -> Result<Option<PathBuf>, Error> {
+        if self.year.is_none() && self.user.is_none() {
+            return Ok(None);
+        }
+
+        let key = store::Key {
+            year: self.year.unwrap_or(store::DEFAULT_YEAR),
+            user: self
+                .user
+                .as_deref()
+                .unwrap_or(store::DEFAULT_USER)
+                .to_string(),
+            day,
+        };
+        store::Store::new(&self.store).resolve(&key).map(Some)
+    }
+}
+
+/// Input for a day run with `--all`: as for a single day, from the store,
+/// unless a root of dayNN folders is given to read `input.txt` from
+fn all_input(root: Option<&Path>, store: &StoreArgs, day: u8) -> Result<PathBuf, Error> {
+    match root {
+        Some(root) => Ok(days::day_dir(root, day).join("input.txt")),
+        None => store.resolve(day),
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum InputCommand {
+    /// Import an input file into the store
+    Add {
+        #[arg(help = "Day of the puzzle", value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(help = "Input file to import")]
+        file: PathBuf,
+
+        #[arg(
+            long = "force",
+            help = "Replace an input already stored",
+            default_value_t = false
+        )]
+        force: bool,
+
+        #[command(flatten)]
+        store: StoreArgs,
+    },
+
+    /// List the inputs in the store
+    List {
+        #[arg(
+            long = "store",
+            env = "AOC_STORE",
+            help = "Directory of stored inputs",
+            default_value_os_t = store::default_root()
+        )]
+        store: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a day's solution against an input file, or every day against its stored input
+    Run {
+        #[arg(help = "Day to run", required_unless_present = "all")]
+        day: Option<u8>,
+
+        #[arg(
+            long = "all",
+            help = "Run both parts of every day, printing a summary",
+            conflicts_with_all = ["day", "part", "stream", "output", "FILE"],
+            default_value_t = false
+        )]
+        all: bool,
+
+        #[arg(
+            short = 'j',
+            long = "jobs",
+            help = "Number of days to run at once with --all (default: one per CPU)",
+            requires = "all"
+        )]
+        jobs: Option<usize>,
+
+        #[arg(
+            long = "root",
+            help = "Directory containing the dayNN folders, to run --all against their input.txt instead of the store",
+            conflicts_with = "day"
+        )]
+        root: Option<PathBuf>,
+
+        #[arg(
+            long = "part",
+            help = "Only run this part (default: both)",
+            value_parser = clap::value_parser!(u8).range(1..=2)
+        )]
+        part: Option<u8>,
+
+        #[arg(
+            long = "time",
+            help = "Report time spent parsing and solving",
+            default_value_t = false
+        )]
+        time: bool,
+
+        #[arg(
+            long = "strict",
+            help = "Fail on malformed lines instead of skipping them",
+            default_value_t = false
+        )]
+        strict: bool,
+
+        #[arg(
+            long = "stream",
+            help = "Read the input line by line instead of loading it into memory",
+            default_value_t = false
+        )]
+        stream: bool,
+
+        #[arg(
+            long = "output",
+            help = "Format to print answers in",
+            value_enum,
+            default_value_t = Output::Text
+        )]
+        output: Output,
+
+        #[arg(
+            help = "Input file, or - for stdin (default: the day's input from the store)",
+            id = "FILE"
+        )]
+        fin: Option<String>,
+
+        #[command(flatten)]
+        store: StoreArgs,
+    },
+
+    /// Manage the store of puzzle inputs
+    Input {
+        #[command(subcommand)]
+        command: InputCommand,
+    },
+
+    /// Check answers for each day's input files against its answers.toml
+    Verify {
+        #[arg(help = "Day to verify (default: all)")]
+        day: Option<u8>,
+
+        #[arg(
+            long = "root",
+            help = "Directory containing the dayNN folders",
+            default_value = WORKSPACE_ROOT
+        )]
+        root: PathBuf,
+
+        #[command(flatten)]
+        stored: StoredInputArgs,
+    },
+
+    /// Create and register a crate for a new day
+    New {
+        #[arg(help = "Day to create", value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(
+            long = "root",
+            help = "Directory containing the dayNN folders",
+            default_value = WORKSPACE_ROOT
+        )]
+        root: PathBuf,
+    },
+
+    /// Re-run a day against its input files whenever its sources or inputs change
+    Watch {
+        #[arg(help = "Day to watch")]
+        day: u8,
+
+        #[arg(
+            long = "root",
+            help = "Directory containing the dayNN folders",
+            default_value = WORKSPACE_ROOT
+        )]
+        root: PathBuf,
+
+        #[command(flatten)]
+        stored: StoredInputArgs,
+    },
+
+    /// Print a random puzzle input for a day
+    Gen {
+        #[arg(help = "Day to generate an input for")]
+        day: u8,
+
+        #[arg(
+            long = "size",
+            help = "Number of records (elves, rounds, moves, ...) to generate",
+            default_value_t = 1000
+        )]
+        size: usize,
+
+        #[arg(
+            long = "seed",
+            help = "Seed for the random generator",
+            default_value_t = 0
+        )]
+        seed: u64,
+    },
+
+    /// Time parsing and solving each day's input.txt (use a release build)
+    Bench {
+        #[arg(help = "Day to benchmark (default: all)")]
+        day: Option<u8>,
+
+        #[arg(
+            short = 'n',
+            long = "iterations",
+            help = "Number of times to run each day",
+            default_value_t = 100
+        )]
+        iterations: usize,
+
+        #[arg(long = "json", help = "Print timings as JSON", default_value_t = false)]
+        json: bool,
+
+        #[arg(long = "save", help = "Save timings as JSON to this file")]
+        save: Option<PathBuf>,
+
+        #[arg(long = "baseline", help = "Compare against timings saved earlier")]
+        baseline: Option<PathBuf>,
+
+        #[arg(
+            long = "threshold",
+            help = "Percentage slowdown of the median to flag",
+            default_value_t = 10.0
+        )]
+        threshold: f64,
+
+        #[arg(
+            long = "root",
+            help = "Directory containing the dayNN folders",
+            default_value = WORKSPACE_ROOT
+        )]
+        root: PathBuf,
+
+        #[command(flatten)]
+        stored: StoredInputArgs,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match init_tracing(cli.verbose, cli.trace.as_deref()).and_then(|_| run(cli)) {
+        Ok(true) => (),
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Run a command, reporting whether it succeeded
+fn run(cli: Cli) -> Result<bool, Error> {
+    match cli.command {
+        Command::Run {
+            all: true,
+            jobs,
+            strict,
+            root,
+            store,
+            ..
+        } => {
+            let jobs =
+                jobs.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+
+            return all::run(&days::DAYS, jobs, strict, |day| {
+                all_input(root.as_deref(), &store, day)
+            });
+        }
+        Command::Run {
+            day: Some(day),
+            part,
+            time,
+            strict,
+            stream,
+            output,
+            fin,
+            store,
+            ..
+        } => {
+            if !days::DAYS.contains(&day) {
+                return Err(Error::Unsolved { day });
+            }
+
+            let fin = match fin {
+                Some(fin) => fin,
+                None => store.resolve(day)?.to_string_lossy().into_owned(),
+            };
+
+            let parts = match part {
+                Some(p) => vec![p],
+                None => vec![1, 2],
+            };
+
+            let mut skipped = Skipped::new(strict);
+            let answers = if stream {
+                stream_answers(day, &fin, &parts, &mut skipped)?
+            } else {
+                days::run(day, &load_input(&fin)?, &parts, &mut skipped)?
+            };
+            if output == Output::Json {
+                for answer in &answers {
+                    println!("{}", answer_json(day, answer));
+                }
+                skipped.report();
+
+                return Ok(true);
+            }
+
+            if time && !stream {
+                if let Some(first) = answers.first() {
+                    println!("Parse: {:?}", first.parse_time);
+                }
+            }
+            for answer in &answers {
+                print_answer(answer.part, &answer.answer);
+                if time && !stream {
+                    println!("Solve: {:?}", answer.solve_time);
+                }
+            }
+            if time && stream {
+                if let Some(first) = answers.first() {
+                    println!("Elapsed: {:?}", first.solve_time);
+                }
+            }
+            skipped.report();
+        }
+        // Clap requires a day unless running all of them
+        Command::Run { day: None, .. } => unreachable!(),
+        Command::Input { command } => input(command)?,
+        Command::Verify { day, root, stored } => {
+            return verify::run(&root, &select_days(day)?, |day| stored.resolve(day))
+        }
+        Command::New { day, root } => {
+            let dir = scaffold::new_day(&root, day)?;
+            println!("Created {}", dir.display());
+            println!("Add the example to example.txt and its answers to answers.toml");
+        }
+        Command::Watch { day, root, stored } => {
+            return watch::run(&root, day, stored.resolve(day)?.as_deref())
+        }
+        Command::Gen { day, size, seed } => {
+            let stdout = std::io::stdout().lock();
+            gen::generate(day, size, seed, &mut std::io::BufWriter::new(stdout))?;
+        }
+        Command::Bench {
+            day,
+            iterations,
+            json,
+            save,
+            baseline,
+            threshold,
+            root,
+            stored,
+        } => {
+            let opts = bench::Options {
+                iterations,
+                json,
+                save: save.as_deref(),
+                baseline: baseline.as_deref(),
+                threshold,
+            };
+
+            return bench::run(&root, &select_days(day)?, &opts, |day| stored.resolve(day));
+        }
+    }
+
+    Ok(true)
+}
+
+/// Import or list stored inputs
+fn input(command: InputCommand) -> Result<(), Error> {
+    match command {
+        InputCommand::Add {
+            day,
+            file,
+            force,
+            store,
+        } => {
+            let path = store::Store::new(&store.store).add(&store.key(day), &file, force)?;
+            println!("Stored {}", path.display());
+        }
+        InputCommand::List { store } => {
+            let entries = store::Store::new(&store).list()?;
+            if entries.is_empty() {
+                eprintln!("No inputs stored in {}", store.display());
+                return Ok(());
+            }
+
+            println!("{:<4}  {:<16} {:>3} {:>8}", "Year", "User", "Day", "Bytes");
+            for e in entries {
+                println!(
+                    "{:<4}  {:<16} {:>3} {:>8}",
+                    e.key.year, e.key.user, e.key.day, e.size
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Solve both parts in a single pass over the input file, reporting the
+/// requested parts with the total time taken
+fn stream_answers(
+    day: u8,
+    fin: &str,
+    parts: &[u8],
+    skipped: &mut Skipped,
+) -> Result<Vec<days::Answer>, Error> {
+    let start = Instant::now();
+    let answers = days::stream(day, open(fin)?, skipped)?;
+    let elapsed = start.elapsed();
+
+    Ok(parts
+        .iter()
+        .map(|&part| days::Answer {
+            part,
+            answer: answers[part as usize - 1].clone(),
+            parse_time: Default::default(),
+            solve_time: elapsed,
+        })
+        .collect())
+}
+
+/// Days to operate on: the one requested, or every registered day
+fn select_days(day: Option<u8>) -> Result<Vec<u8>, Error> {
+    match day {
+        Some(d) if !days::DAYS.contains(&d) => Err(Error::Unsolved { day: d }),
+        Some(d) => Ok(vec![d]),
+        None => Ok(days::DAYS.to_vec()),
+    }
+}
+
+/// An answer as reported by `--output json`
+#[derive(Serialize)]
+struct JsonAnswer<'a> {
+    day: u8,
+    part: u8,
+    answer: &'a str,
+    /// Time spent parsing the input and solving this part
+    elapsed_ns: u64,
+}
+
+/// Format an answer as a single line of JSON
+fn answer_json(day: u8, answer: &days::Answer) -> String {
+    let json = JsonAnswer {
+        day,
+        part: answer.part,
+        answer: &answer.answer,
+        elapsed_ns: (answer.parse_time + answer.solve_time).as_nanos() as u64,
+    };
+
+    serde_json::to_string(&json).expect("answers serialize to JSON")
+}
+
+/// Print an answer, starting multi-line answers (e.g. images) on their own line
+fn print_answer(part: u8, answer: &str) {
+    if answer.contains('\n') {
+        println!("Part {}:\n{}", part, answer);
+    } else {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_answer_json() {
+        let answer = days::Answer {
+            part: 2,
+            answer: "#.\n.#".to_string(),
+            parse_time: Duration::from_nanos(100),
+            solve_time: Duration::from_nanos(23),
+        };
+
+        assert_eq!(
+            answer_json(10, &answer),
+            r##"{"day":10,"part":2,"answer":"#.\n.#","elapsed_ns":123}"##
+        );
+    }
+
+    #[test]
+    fn test_all_input() {
+        let root = std::env::temp_dir().join(format!("aoc-all-input-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let store = StoreArgs {
+            store: root.join("store"),
+            year: 2022,
+            user: "ann".to_string(),
+        };
+
+        // The store is used unless a root is given, as for a single day
+        assert!(matches!(
+            all_input(None, &store, 1),
+            Err(Error::Open { .. })
+        ));
+        let file = root.join("day01.txt");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(&file, "1000\n").unwrap();
+        let stored = store::Store::new(&store.store)
+            .add(&store.key(1), &file, false)
+            .unwrap();
+        assert_eq!(all_input(None, &store, 1).unwrap(), stored);
+
+        assert_eq!(
+            all_input(Some(&root), &store, 1).unwrap(),
+            root.join("day01").join("input.txt")
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_stored_input() {
+        let root = std::env::temp_dir().join(format!("aoc-stored-input-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let mut stored = StoredInputArgs {
+            store: root.join("store"),
+            year: None,
+            user: None,
+        };
+
+        // input.txt is used unless a year or user is given
+        assert_eq!(stored.resolve(1).unwrap(), None);
+
+        stored.user = Some("ann".to_string());
+        assert!(matches!(stored.resolve(1), Err(Error::Open { .. })));
+
+        let file = root.join("day01.txt");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(&file, "1000\n").unwrap();
+        let key = store::Key {
+            year: store::DEFAULT_YEAR,
+            user: "ann".to_string(),
+            day: 1,
+        };
+        let path = store::Store::new(&stored.store)
+            .add(&key, &file, false)
+            .unwrap();
+        assert_eq!(stored.resolve(1).unwrap(), Some(path));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,274 @@
+use aoc_common::Error;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::days::day_dir;
+
+/// Files of a new day's crate, relative to its directory, and their templates
+const TEMPLATES: [(&str, &str); 5] = [
+    (
+        "Cargo.toml",
+        include_str!("../templates/day/Cargo.toml.tmpl"),
+    ),
+    ("src/main.rs", include_str!("../templates/day/main.rs.tmpl")),
+    ("src/lib.rs", include_str!("../templates/day/lib.rs.tmpl")),
+    (
+        "answers.toml",
+        include_str!("../templates/day/answers.toml.tmpl"),
+    ),
+    ("example.txt", ""),
+];
+
+/// Fill in a template for a day
+fn render(template: &str, day: u8) -> String {
+    template.replace("{{day}}", &day.to_string())
+}
+
+/// Insert a line among a sorted run of lines, each of which belongs to a day.
+///
+/// `day_of` picks out the day of a line in the run (and ignores other lines).
+/// The new line goes after the last line for an earlier day, or before the
+/// first line of the run. Returns `None` if there is no such run of lines.
+fn insert_sorted<F>(text: &str, day: u8, line: &str, day_of: F) -> Option<String>
+where
+    F: Fn(&str) -> Option<u8>,
+{
+    let lines: Vec<&str> = text.lines().collect();
+    let entries: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| day_of(l).map(|d| (i, d)))
+        .collect();
+
+    let at = match entries.iter().rev().find(|&&(_, d)| d < day) {
+        Some(&(i, _)) => i + 1,
+        None => entries.first()?.0,
+    };
+
+    let mut out: Vec<&str> = lines[..at].to_vec();
+    out.push(line);
+    out.extend(&lines[at..]);
+
+    Some(out.join("\n") + "\n")
+}
+
+/// Number following a prefix, up to the given terminator
+fn number_between(line: &str, prefix: &str, terminator: &str) -> Option<u8> {
+    line.strip_prefix(prefix)?
+        .split_once(terminator)?
+        .0
+        .parse()
+        .ok()
+}
+
+/// Add the day's crate to the workspace's members
+fn add_member(manifest: &str, day: u8) -> Option<String> {
+    let line = format!("    \"day{:02}\",", day);
+
+    insert_sorted(manifest, day, &line, |l| {
+        number_between(l, "    \"day", "\",")
+    })
+}
+
+/// Add the day's crate to the dependencies of the runner or another crate,
+/// with any `options` for the dependency
+fn add_dependency(manifest: &str, day: u8, options: &str) -> Option<String> {
+    let line = format!("day{} = {{ path = \"../day{:02}\"{} }}", day, day, options);
+
+    insert_sorted(manifest, day, &line, |l| number_between(l, "day", " = "))
+}
+
+/// Register the day's solution in a list of days and the dispatch which calls
+/// `solver` (e.g. `run_solution`) for each day
+fn register_day(source: &str, day: u8, solver: &str) -> Option<String> {
+    const DAYS: &str = "pub const DAYS: [u8; ";
+
+    let old = source.lines().find(|l| l.starts_with(DAYS))?;
+    let list = old.split_once("= [")?.1.strip_suffix("];")?;
+    let mut days: Vec<u8> = list
+        .split(", ")
+        .map(|d| d.parse().ok())
+        .collect::<Option<_>>()?;
+    days.push(day);
+    days.sort_unstable();
+
+    let listed: Vec<String> = days.iter().map(|d| d.to_string()).collect();
+    let new = format!("{}{}] = [{}];", DAYS, days.len(), listed.join(", "));
+    let source = source.replacen(old, &new, 1);
+
+    // Each arm passes the same arguments, so copy them from an existing one
+    let call = format!("=> {}::<", solver);
+    let existing = source.lines().find(|l| l.contains(&call))?;
+    let args = &existing[existing.find(">(")?..];
+    let arm = format!("        {} {}day{}::Day{}{}", day, call, day, day, args);
+
+    insert_sorted(&source, day, &arm, |l| {
+        l.contains(&call)
+            .then(|| number_between(l.trim_start(), "", " =>"))
+            .flatten()
+    })
+}
+
+fn open_error(path: &Path) -> impl FnOnce(io::Error) -> Error + '_ {
+    move |source| Error::Open {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+/// Apply an edit to a file, failing if the edit can't find its place
+fn edit<F>(path: &Path, f: F) -> Result<String, Error>
+where
+    F: FnOnce(&str) -> Option<String>,
+{
+    let text = fs::read_to_string(path).map_err(open_error(path))?;
+
+    f(&text).ok_or_else(|| {
+        open_error(path)(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "couldn't find where to register the new day",
+        ))
+    })
+}
+
+/// Create a crate for a new day from the templates, and register it with the
+/// workspace, the runner and the WebAssembly facade. Returns the new crate's
+/// directory.
+pub fn new_day(root: &Path, day: u8) -> Result<PathBuf, Error> {
+    let dir = day_dir(root, day);
+    if dir.exists() {
+        return Err(open_error(&dir)(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "day already exists",
+        )));
+    }
+
+    // Work out every edit before changing anything
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("aoc/Cargo.toml");
+    let days = root.join("aoc/src/days.rs");
+    let wasm = root.join("wasm/Cargo.toml");
+    let wasm_lib = root.join("wasm/src/lib.rs");
+    let edits = [
+        (&workspace, edit(&workspace, |s| add_member(s, day))?),
+        (&runner, edit(&runner, |s| add_dependency(s, day, ""))?),
+        (
+            &days,
+            edit(&days, |s| register_day(s, day, "run_solution"))?,
+        ),
+        (
+            &wasm,
+            edit(&wasm, |s| {
+                add_dependency(s, day, ", default-features = false")
+            })?,
+        ),
+        (
+            &wasm_lib,
+            edit(&wasm_lib, |s| register_day(s, day, "solve_both"))?,
+        ),
+    ];
+
+    for (file, template) in TEMPLATES {
+        let path = dir.join(file);
+        let parent = path
+            .parent()
+            .expect("templates are inside the day's directory");
+        fs::create_dir_all(parent).map_err(open_error(parent))?;
+        fs::write(&path, render(template, day)).map_err(open_error(&path))?;
+    }
+
+    for (path, text) in edits {
+        fs::write(path, text).map_err(open_error(path))?;
+    }
+
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_member() {
+        let manifest =
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day09\",\n    \"day10\",\n]\n";
+
+        assert_eq!(
+            add_member(manifest, 11).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day09\",\n    \"day10\",\n    \"day11\",\n]\n"
+        );
+        assert_eq!(
+            add_member(manifest, 1).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day09\",\n    \"day10\",\n]\n"
+        );
+        assert_eq!(add_member("[workspace]\n", 1), None);
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let manifest =
+            "toml = \"0.8\"\nday1 = { path = \"../day01\" }\nday10 = { path = \"../day10\" }\n";
+
+        assert_eq!(
+            add_dependency(manifest, 2, "").unwrap(),
+            "toml = \"0.8\"\nday1 = { path = \"../day01\" }\nday2 = { path = \"../day02\" }\nday10 = { path = \"../day10\" }\n"
+        );
+        assert!(add_dependency(manifest, 2, ", default-features = false")
+            .unwrap()
+            .contains("day2 = { path = \"../day02\", default-features = false }\n"));
+    }
+
+    #[test]
+    fn test_register_day() {
+        let source = "\
+pub const DAYS: [u8; 2] = [1, 10];
+
+fn run() {
+    match day {
+        1 => run_solution::<day1::Day1>(input, parts, skipped),
+        10 => run_solution::<day10::Day10>(input, parts, skipped),
+        _ => Err(Error::Unsolved { day }),
+    }
+}
+";
+
+        assert_eq!(
+            register_day(source, 11, "run_solution").unwrap(),
+            "\
+pub const DAYS: [u8; 3] = [1, 10, 11];
+
+fn run() {
+    match day {
+        1 => run_solution::<day1::Day1>(input, parts, skipped),
+        10 => run_solution::<day10::Day10>(input, parts, skipped),
+        11 => run_solution::<day11::Day11>(input, parts, skipped),
+        _ => Err(Error::Unsolved { day }),
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_register_with_runner() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+
+        assert!(add_member(&read("Cargo.toml"), 11).is_some());
+        assert!(add_dependency(&read("aoc/Cargo.toml"), 11, "").is_some());
+        assert!(register_day(&read("aoc/src/days.rs"), 11, "run_solution").is_some());
+        assert!(add_dependency(&read("wasm/Cargo.toml"), 11, "").is_some());
+        assert!(register_day(&read("wasm/src/lib.rs"), 11, "solve_both").is_some());
+    }
+
+    #[test]
+    fn test_render() {
+        let lib = render(TEMPLATES[2].1, 11);
+
+        assert!(lib.contains("pub struct Day11;"));
+        assert!(lib.contains("Error::Unsolved { day: 11 }"));
+        assert!(!lib.contains("{{"));
+    }
+}
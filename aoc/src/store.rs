@@ -0,0 +1,244 @@
+use aoc_common::Error;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Calendar year of the puzzles solved in this repository
+pub const DEFAULT_YEAR: u16 = 2022;
+
+/// Account name used when none is given
+pub const DEFAULT_USER: &str = "default";
+
+/// Identifies one puzzle input: each account gets its own input for each day
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub year: u16,
+    pub user: String,
+    pub day: u8,
+}
+
+/// An input held in the store, with its size in bytes
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+    pub key: Key,
+    pub size: u64,
+}
+
+/// Puzzle inputs kept on the local filesystem, laid out as
+/// `<root>/<year>/<user>/dayNN.txt`
+pub struct Store {
+    root: PathBuf,
+}
+
+/// Default location of the store: `$XDG_DATA_HOME/aoc/inputs`, falling back to
+/// `~/.local/share/aoc/inputs`
+pub fn default_root() -> PathBuf {
+    let data = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+        .unwrap_or_default();
+
+    data.join("aoc").join("inputs")
+}
+
+/// Check that a user name can safely be used as a directory name
+pub fn parse_user(user: &str) -> Result<String, String> {
+    let valid = !user.is_empty()
+        && !user.starts_with('.')
+        && user
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.@".contains(c));
+
+    match valid {
+        true => Ok(user.to_string()),
+        false => Err("user names may only use letters, digits and -_.@".to_string()),
+    }
+}
+
+fn open_error(path: &Path) -> impl FnOnce(io::Error) -> Error + '_ {
+    move |source| Error::Open {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+/// Day of a stored input's file name, like `day07.txt`
+fn file_day(name: &str) -> Option<u8> {
+    let digits = name.strip_prefix("day")?.strip_suffix(".txt")?;
+
+    match digits.len() {
+        2 => digits.parse().ok(),
+        _ => None,
+    }
+}
+
+impl Store {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Store { root: root.into() }
+    }
+
+    /// Where the input for a key is (or would be) kept
+    pub fn path(&self, key: &Key) -> PathBuf {
+        self.root
+            .join(key.year.to_string())
+            .join(&key.user)
+            .join(format!("day{:02}.txt", key.day))
+    }
+
+    /// Find the input for a key, failing if it hasn't been added
+    pub fn resolve(&self, key: &Key) -> Result<PathBuf, Error> {
+        let path = self.path(key);
+
+        match path.is_file() {
+            true => Ok(path),
+            false => Err(open_error(&path)(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no input stored (add one with `aoc input add`)",
+            ))),
+        }
+    }
+
+    /// Copy an input file into the store, refusing to replace an existing
+    /// input unless `force` is set. Returns the stored input's path.
+    pub fn add(&self, key: &Key, file: &Path, force: bool) -> Result<PathBuf, Error> {
+        let path = self.path(key);
+        if path.exists() && !force {
+            return Err(open_error(&path)(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "input already stored (use --force to replace it)",
+            )));
+        }
+
+        let dir = path.parent().expect("stored inputs are inside a directory");
+        fs::create_dir_all(dir).map_err(open_error(dir))?;
+        fs::copy(file, &path).map_err(open_error(file))?;
+
+        Ok(path)
+    }
+
+    /// List every stored input, sorted by year, user and day
+    pub fn list(&self) -> Result<Vec<Entry>, Error> {
+        let mut entries = Vec::new();
+
+        for year_dir in read_dir(&self.root)? {
+            let Some(year) = dir_name(&year_dir).and_then(|name| name.parse().ok()) else {
+                continue;
+            };
+
+            for user_dir in read_dir(&year_dir)? {
+                let Some(user) = dir_name(&user_dir) else {
+                    continue;
+                };
+
+                for file in read_dir(&user_dir)? {
+                    let Some(day) = dir_name(&file).and_then(file_day) else {
+                        continue;
+                    };
+                    let size = fs::metadata(&file).map_err(open_error(&file))?.len();
+
+                    let key = Key {
+                        year,
+                        user: user.to_string(),
+                        day,
+                    };
+                    entries.push(Entry { key, size });
+                }
+            }
+        }
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(entries)
+    }
+}
+
+/// Paths in a directory, treating a missing directory as empty
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    match fs::read_dir(dir) {
+        Ok(entries) => Ok(entries
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(open_error(dir)(e)),
+    }
+}
+
+fn dir_name(path: &Path) -> Option<&str> {
+    path.file_name()?.to_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(year: u16, user: &str, day: u8) -> Key {
+        Key {
+            year,
+            user: user.to_string(),
+            day,
+        }
+    }
+
+    /// A fresh, empty store in the system's temporary directory
+    fn temp_store(name: &str) -> Store {
+        let root = std::env::temp_dir().join(format!("aoc-store-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&root);
+
+        Store::new(root)
+    }
+
+    #[test]
+    fn test_add_and_resolve() {
+        let store = temp_store("add");
+        let file = store.root.with_extension("txt");
+        fs::write(&file, "1000\n2000\n").unwrap();
+
+        assert!(matches!(
+            store.resolve(&key(2022, "ann", 1)),
+            Err(Error::Open { .. })
+        ));
+
+        let path = store.add(&key(2022, "ann", 1), &file, false).unwrap();
+        assert!(path.ends_with("2022/ann/day01.txt"));
+        assert_eq!(store.resolve(&key(2022, "ann", 1)).unwrap(), path);
+        assert!(store.resolve(&key(2022, "bob", 1)).is_err());
+
+        assert!(store.add(&key(2022, "ann", 1), &file, false).is_err());
+        assert!(store.add(&key(2022, "ann", 1), &file, true).is_ok());
+    }
+
+    #[test]
+    fn test_list() {
+        let store = temp_store("list");
+        assert_eq!(store.list().unwrap(), []);
+
+        let file = store.root.with_extension("txt");
+        fs::write(&file, "abc\n").unwrap();
+        for k in [
+            key(2023, "ann", 2),
+            key(2022, "bob", 10),
+            key(2022, "ann", 3),
+        ] {
+            store.add(&k, &file, false).unwrap();
+        }
+        fs::write(store.root.join("2022/ann/notes.md"), "").unwrap();
+
+        let keys: Vec<Key> = store.list().unwrap().into_iter().map(|e| e.key).collect();
+        assert_eq!(
+            keys,
+            [
+                key(2022, "ann", 3),
+                key(2022, "bob", 10),
+                key(2023, "ann", 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_user() {
+        assert_eq!(parse_user("ann-2").unwrap(), "ann-2");
+        assert!(parse_user("").is_err());
+        assert!(parse_user("..").is_err());
+        assert!(parse_user("a/b").is_err());
+    }
+}
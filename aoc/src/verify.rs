@@ -0,0 +1,263 @@
+use aoc_common::{load_input, Error, Skipped};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::days::{self, day_dir};
+
+/// Name of the file in each day's directory recording its expected answers
+pub const MANIFEST: &str = "answers.toml";
+
+/// Expected answers for one input file
+#[derive(Deserialize, Debug, Default)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Expected answers for each of a day's input files, keyed by file stem
+/// (e.g. `input` for `input.txt`)
+pub type Manifest = BTreeMap<String, Expected>;
+
+/// Outcome of checking one part against one input file
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+    Error(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {:?}, got {:?})", expected, actual)
+            }
+            Status::Missing => write!(f, "missing"),
+            Status::Error(e) => write!(f, "ERROR ({})", e),
+        }
+    }
+}
+
+/// Result of checking one part of a day against one input file
+pub struct Check {
+    pub day: u8,
+    pub file: String,
+    pub part: u8,
+    pub status: Status,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {:>2} {:<10} part {}: {}",
+            self.day, self.file, self.part, self.status
+        )
+    }
+}
+
+/// Load a day's expected answers, treating a missing manifest as empty
+pub fn load_manifest(path: &Path) -> Result<Manifest, Error> {
+    match fs::read_to_string(path) {
+        Ok(s) => toml::from_str(&s).map_err(|e| Error::Open {
+            path: path.display().to_string(),
+            source: io::Error::new(io::ErrorKind::InvalidData, e),
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Manifest::new()),
+        Err(source) => Err(Error::Open {
+            path: path.display().to_string(),
+            source,
+        }),
+    }
+}
+
+/// List a day's puzzle inputs by file stem: `input.txt` and any
+/// `example*.txt`. An input from the store, if given, stands in for
+/// `input.txt`.
+pub fn input_files(dir: &Path, stored: Option<&Path>) -> Result<Vec<(String, PathBuf)>, Error> {
+    let entries = fs::read_dir(dir).map_err(|source| Error::Open {
+        path: dir.display().to_string(),
+        source,
+    })?;
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let stem = match path.file_stem().and_then(|stem| stem.to_str()) {
+            Some(stem) if path.extension().is_some_and(|ext| ext == "txt") => stem.to_string(),
+            _ => continue,
+        };
+
+        let is_input = (stem == "input" && stored.is_none()) || stem.starts_with("example");
+        if is_input {
+            files.push((stem, path));
+        }
+    }
+    files.extend(stored.map(|path| ("input".to_string(), path.to_path_buf())));
+    files.sort();
+
+    Ok(files)
+}
+
+/// Compare an answer (or the error produced instead) with the recorded one
+pub fn check(expected: Option<&String>, actual: Result<&str, &Error>) -> Status {
+    match (expected, actual) {
+        (None, _) => Status::Missing,
+        (Some(_), Err(e)) => Status::Error(e.to_string()),
+        (Some(expected), Ok(actual)) => {
+            if expected.trim_end() == actual.trim_end() {
+                Status::Pass
+            } else {
+                Status::Fail {
+                    expected: expected.clone(),
+                    actual: actual.to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// Run both parts of a day against each of its input files, with `stored`
+/// standing in for `input.txt` if given
+pub fn verify_day(root: &Path, day: u8, stored: Option<&Path>) -> Result<Vec<Check>, Error> {
+    let dir = day_dir(root, day);
+    let manifest = load_manifest(&dir.join(MANIFEST))?;
+
+    let mut checks = Vec::new();
+    for (file, path) in input_files(&dir, stored)? {
+        let expected = manifest.get(&file);
+
+        let input = load_input(&path.to_string_lossy())?;
+        // Checked-in inputs are expected to be well formed
+        let answers = days::run(day, &input, &[1, 2], &mut Skipped::new(true));
+
+        for part in [1, 2] {
+            let expected = expected.and_then(|e| match part {
+                1 => e.part1.as_ref(),
+                _ => e.part2.as_ref(),
+            });
+            let actual = match &answers {
+                Ok(answers) => Ok(answers[usize::from(part) - 1].answer.as_str()),
+                Err(e) => Err(e),
+            };
+
+            checks.push(Check {
+                day,
+                file: file.clone(),
+                part,
+                status: check(expected, actual),
+            });
+        }
+    }
+
+    Ok(checks)
+}
+
+/// Verify each of the given days, printing a line per check and a summary.
+/// `stored` gives the input from the store to check in place of a day's
+/// `input.txt`, if any. Returns whether every recorded answer was reproduced.
+pub fn run(
+    root: &Path,
+    days: &[u8],
+    stored: impl Fn(u8) -> Result<Option<PathBuf>, Error>,
+) -> Result<bool, Error> {
+    let (mut n_pass, mut n_fail, mut n_missing) = (0, 0, 0);
+
+    for &day in days {
+        for check in verify_day(root, day, stored(day)?.as_deref())? {
+            println!("{}", check);
+
+            match check.status {
+                Status::Pass => n_pass += 1,
+                Status::Missing => n_missing += 1,
+                _ => n_fail += 1,
+            }
+        }
+    }
+
+    println!(
+        "{} passed, {} failed, {} missing",
+        n_pass, n_fail, n_missing
+    );
+
+    Ok(n_fail == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_manifest() {
+        let manifest: Manifest = toml::from_str(
+            "[input]\npart1 = \"24000\"\npart2 = \"45000\"\n\n[example2]\npart2 = '''\n#.\n.#'''\n",
+        )
+        .unwrap();
+
+        assert_eq!(manifest["input"].part1.as_deref(), Some("24000"));
+        assert_eq!(manifest["example2"].part1, None);
+        assert_eq!(manifest["example2"].part2.as_deref(), Some("#.\n.#"));
+    }
+
+    #[test]
+    fn test_check() {
+        let expected = "24000".to_string();
+        let error = Error::Unsolved { day: 7 };
+
+        assert_eq!(check(Some(&expected), Ok("24000")), Status::Pass);
+        assert_eq!(check(Some(&expected), Ok("24000\n")), Status::Pass);
+        assert_eq!(
+            check(Some(&expected), Ok("41000")),
+            Status::Fail {
+                expected: "24000".to_string(),
+                actual: "41000".to_string()
+            }
+        );
+        assert_eq!(check(None, Ok("41000")), Status::Missing);
+        assert_eq!(check(None, Err(&error)), Status::Missing);
+        assert!(matches!(
+            check(Some(&expected), Err(&error)),
+            Status::Error(_)
+        ));
+    }
+
+    #[test]
+    fn test_input_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-files-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for file in [
+            "input.txt",
+            "example.txt",
+            "example2.txt",
+            "notes.txt",
+            "answers.toml",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let files = input_files(&dir, None).unwrap();
+        assert_eq!(
+            files
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            ["example", "example2", "input"]
+        );
+        assert_eq!(files[2].1, dir.join("input.txt"));
+
+        // A stored input replaces input.txt under the same name
+        let stored = Path::new("/store/2022/ann/day01.txt");
+        let files = input_files(&dir, Some(stored)).unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[2], ("input".to_string(), stored.to_path_buf()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,291 @@
+use aoc_common::Error;
+use notify::{event::ModifyKind, Event, EventKind, RecursiveMode, Watcher};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap, ffi::OsString, io, path::Path, process::Command, sync::mpsc::Receiver,
+    time::Duration,
+};
+
+use crate::{
+    days::day_dir,
+    verify::{input_files, load_manifest, Expected, Manifest, MANIFEST},
+};
+
+/// How long to wait for a burst of changes (e.g. an editor saving several
+/// files) to settle before re-running
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// Answers to both parts for one input file, or the error printed instead
+pub type Answers = Result<[String; 2], String>;
+
+/// One line printed by `aoc run --output json`
+#[derive(Deserialize)]
+struct JsonAnswer {
+    part: u8,
+    answer: String,
+}
+
+fn cargo() -> OsString {
+    std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into())
+}
+
+/// Build the runner, returning the compiler's output if that fails
+fn build(root: &Path) -> Result<(), String> {
+    let output = Command::new(cargo())
+        .args(["build", "--quiet", "--package", "aoc", "--manifest-path"])
+        .arg(root.join("Cargo.toml"))
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    match output.status.success() {
+        true => Ok(()),
+        false => Err(String::from_utf8_lossy(&output.stderr)
+            .trim_end()
+            .to_string()),
+    }
+}
+
+/// Run both parts of a day against an input file with the freshly built runner
+fn run_file(root: &Path, day: u8, file: &Path) -> Answers {
+    let output = Command::new(cargo())
+        .args(["run", "--quiet", "--package", "aoc", "--manifest-path"])
+        .arg(root.join("Cargo.toml"))
+        .args(["--", "run", &day.to_string(), "--output", "json"])
+        .arg(file)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr)
+            .trim_end()
+            .to_string());
+    }
+
+    let mut answers = [String::new(), String::new()];
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let json: JsonAnswer = serde_json::from_str(line).map_err(|e| e.to_string())?;
+        if let Some(answer) = answers.get_mut(usize::from(json.part).wrapping_sub(1)) {
+            *answer = json.answer;
+        }
+    }
+
+    Ok(answers)
+}
+
+/// Line-by-line differences between two answers, marking lines of the old
+/// answer with `-` and lines of the new one with `+`
+pub fn diff(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let mut lines = Vec::new();
+    for i in 0..old.len().max(new.len()) {
+        let (a, b) = (old.get(i), new.get(i));
+        if a == b {
+            continue;
+        }
+        lines.extend(a.map(|a| format!("- {}", a)));
+        lines.extend(b.map(|b| format!("+ {}", b)));
+    }
+
+    lines
+}
+
+/// Describe one input file's answers, comparing each part with the expected
+/// answer and with the answer from the previous run
+pub fn report(
+    file: &str,
+    current: &Answers,
+    previous: Option<&Answers>,
+    expected: Option<&Expected>,
+) -> String {
+    let answers = match current {
+        Ok(answers) => answers,
+        Err(e) => return format!("{}: error\n  {}\n", file, e.replace('\n', "\n  ")),
+    };
+
+    let mut out = String::new();
+    for (i, answer) in answers.iter().enumerate() {
+        match answer.contains('\n') {
+            true => out += &format!("{} part {}:\n{}\n", file, i + 1, answer),
+            false => out += &format!("{} part {}: {}\n", file, i + 1, answer),
+        }
+
+        let wanted = expected.and_then(|e| [&e.part1, &e.part2][i].as_deref());
+        let before = match previous {
+            Some(Ok(before)) => Some(before[i].as_str()),
+            _ => None,
+        };
+
+        for (label, other) in [("expected", wanted), ("previous", before)] {
+            let status = match other {
+                None if label == "expected" => "missing".to_string(),
+                None => "new".to_string(),
+                Some(other) if other.trim_end() == answer.trim_end() => "same".to_string(),
+                Some(other) => {
+                    let lines = diff(other.trim_end(), answer.trim_end());
+                    format!("differs\n    {}", lines.join("\n    "))
+                }
+            };
+            out += &format!("  vs {}: {}\n", label, status);
+        }
+    }
+
+    out
+}
+
+/// Build and run a day against each of its input files, with `stored`
+/// standing in for `input.txt` if given, printing how the answers compare.
+/// Returns the answers, keyed by file stem.
+fn run_once(
+    root: &Path,
+    day: u8,
+    stored: Option<&Path>,
+    previous: &BTreeMap<String, Answers>,
+) -> Result<BTreeMap<String, Answers>, Error> {
+    let dir = day_dir(root, day);
+    println!("\n=== Day {} ===", day);
+
+    if let Err(e) = build(root) {
+        println!("Build failed:\n{}", e);
+        return Ok(previous.clone());
+    }
+
+    // A manifest may be caught half-edited, which shouldn't stop the watch
+    let manifest = load_manifest(&dir.join(MANIFEST)).unwrap_or_else(|e| {
+        println!("{}", e);
+        Manifest::new()
+    });
+
+    let mut current = BTreeMap::new();
+    for (file, path) in input_files(&dir, stored)? {
+        let answers = run_file(root, day, &path);
+        print!(
+            "{}",
+            report(&file, &answers, previous.get(&file), manifest.get(&file))
+        );
+        current.insert(file, answers);
+    }
+
+    Ok(current)
+}
+
+/// Whether an event changed a source file, input or manifest
+fn is_relevant(event: &Event) -> bool {
+    let changed = match event.kind {
+        EventKind::Modify(ModifyKind::Metadata(_)) => false,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => true,
+        _ => false,
+    };
+
+    changed
+        && event.paths.iter().any(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == "rs" || ext == "txt" || ext == "toml")
+        })
+}
+
+fn watch_error(path: &Path) -> impl FnOnce(notify::Error) -> Error + '_ {
+    move |e| Error::Open {
+        path: path.display().to_string(),
+        source: io::Error::other(e),
+    }
+}
+
+/// Block until a relevant change, then until the burst of changes settles
+fn wait_for_change(dir: &Path, events: &Receiver<notify::Result<Event>>) -> Result<(), Error> {
+    loop {
+        // The watcher sending events lives as long as the watch
+        let event = events.recv().expect("watcher stopped");
+        if is_relevant(&event.map_err(watch_error(dir))?) {
+            break;
+        }
+    }
+    while events.recv_timeout(SETTLE_TIME).is_ok() {}
+
+    Ok(())
+}
+
+/// Re-run a day against its inputs whenever its sources, inputs or expected
+/// answers change. An input from the store, if given, is run in place of
+/// `input.txt`. Runs until interrupted.
+pub fn run(root: &Path, day: u8, stored: Option<&Path>) -> Result<bool, Error> {
+    let dir = day_dir(root, day);
+    let (tx, events) = std::sync::mpsc::channel();
+
+    let mut watcher = notify::recommended_watcher(tx).map_err(watch_error(&dir))?;
+    watcher
+        .watch(&dir, RecursiveMode::Recursive)
+        .map_err(watch_error(&dir))?;
+
+    let mut previous = BTreeMap::new();
+    loop {
+        previous = run_once(root, day, stored, &previous)?;
+        println!("Watching {} for changes...", dir.display());
+        wait_for_change(&dir, &events)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, DataChange};
+    use std::path::PathBuf;
+
+    fn answers(part1: &str, part2: &str) -> Answers {
+        Ok([part1.to_string(), part2.to_string()])
+    }
+
+    #[test]
+    fn test_diff() {
+        assert!(diff("24000", "24000").is_empty());
+        assert_eq!(diff("24000", "41000"), ["- 24000", "+ 41000"]);
+        assert_eq!(diff("#.\n.#", "#.\n##\n.."), ["- .#", "+ ##", "+ .."]);
+    }
+
+    #[test]
+    fn test_report() {
+        let expected = Expected {
+            part1: Some("24000".to_string()),
+            part2: None,
+        };
+        let previous = answers("24000", "41000");
+
+        let out = report(
+            "example",
+            &answers("24000", "45000"),
+            Some(&previous),
+            Some(&expected),
+        );
+        assert_eq!(
+            out,
+            "example part 1: 24000\n  vs expected: same\n  vs previous: same\n\
+             example part 2: 45000\n  vs expected: missing\n  vs previous: differs\n    - 41000\n    + 45000\n"
+        );
+
+        let out = report(
+            "input",
+            &Err("Day 7 is not solved yet".to_string()),
+            None,
+            None,
+        );
+        assert_eq!(out, "input: error\n  Day 7 is not solved yet\n");
+    }
+
+    #[test]
+    fn test_is_relevant() {
+        let event = |kind, path: &str| Event::new(kind).add_path(PathBuf::from(path));
+        let write = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+
+        assert!(is_relevant(&event(write, "day01/src/lib.rs")));
+        assert!(is_relevant(&event(
+            EventKind::Create(CreateKind::File),
+            "day01/example2.txt"
+        )));
+        assert!(!is_relevant(&event(write, "day01/src/.lib.rs.swp")));
+        assert!(!is_relevant(&event(
+            EventKind::Access(AccessKind::Any),
+            "day01/input.txt"
+        )));
+    }
+}
@@ -0,0 +1,49 @@
+use clap::{ArgAction, Parser, ValueEnum};
+use std::path::PathBuf;
+
+use crate::{init_tracing, Error};
+
+/// Standard command-line arguments accepted by each day's binary
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[arg(help = "Input file", id = "FILE", default_value = "-")]
+    pub fin: String,
+
+    #[arg(long = "part2", help = "Use part2 logic", default_value_t = false)]
+    pub part2: bool,
+
+    #[arg(
+        long = "strict",
+        help = "Fail on malformed lines instead of skipping them",
+        default_value_t = false
+    )]
+    pub strict: bool,
+
+    #[arg(
+        short = 'v',
+        long = "verbose",
+        help = "Trace solver internals (-v for debug, -vv for trace)",
+        action = ArgAction::Count
+    )]
+    pub verbose: u8,
+
+    #[arg(long = "trace", help = "Write trace events as JSON to this file")]
+    pub trace: Option<PathBuf>,
+}
+
+/// How answers are printed
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Output {
+    /// Human-readable lines like "Part 1: 42"
+    Text,
+    /// One JSON object per answer
+    Json,
+}
+
+/// Parse command-line arguments, and set up tracing as they request
+pub fn get_args() -> Result<Args, Error> {
+    let args = Args::parse();
+    init_tracing(args.verbose, args.trace.as_deref())?;
+
+    Ok(args)
+}
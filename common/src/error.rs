@@ -0,0 +1,70 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::ParseError;
+
+/// Errors encountered while loading or solving a puzzle
+#[derive(Debug)]
+pub enum Error {
+    /// An input file could not be opened or understood
+    #[cfg(feature = "std")]
+    Open { path: String, source: io::Error },
+    /// An input could not be read
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    /// An input did not match the puzzle's expected format
+    Parse(ParseError),
+    /// The puzzle does not have a solution yet
+    Unsolved { day: u8 },
+    /// The puzzle's solution cannot be run in the requested way
+    Unsupported { day: u8, feature: &'static str },
+    /// A value computed while solving does not fit in the integer type chosen
+    /// to hold it
+    Overflow { day: u8, width: &'static str },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            Error::Open { path, source } => write!(f, "{}: {}", path, source),
+            #[cfg(feature = "std")]
+            Error::Io(e) => write!(f, "Failed to read input: {}", e),
+            Error::Parse(e) => write!(f, "Invalid input: {}", e),
+            Error::Unsolved { day } => write!(f, "Day {} is not solved yet", day),
+            Error::Unsupported { day, feature } => {
+                write!(f, "Day {} does not support {}", day, feature)
+            }
+            Error::Overflow { day, width } => {
+                write!(f, "Day {}'s arithmetic overflows {}", day, width)
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            Error::Open { source, .. } => Some(source),
+            #[cfg(feature = "std")]
+            Error::Io(e) => Some(e),
+            Error::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
@@ -0,0 +1,180 @@
+use flate2::bufread::MultiGzDecoder;
+use memmap2::Mmap;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    ops::Deref,
+    str,
+};
+
+use crate::Error;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Open an input file for reading, or stdin if the filename is "-".
+///
+/// Gzip- and zstd-compressed inputs are detected by their magic bytes and
+/// decompressed transparently.
+pub fn open(filename: &str) -> Result<Box<dyn BufRead>, Error> {
+    let reader: Box<dyn BufRead> = match filename {
+        "-" => Box::new(BufReader::new(io::stdin())),
+        _ => Box::new(BufReader::new(File::open(filename).map_err(|source| {
+            Error::Open {
+                path: filename.to_string(),
+                source,
+            }
+        })?)),
+    };
+
+    decompress(reader).map_err(|source| Error::Open {
+        path: filename.to_string(),
+        source,
+    })
+}
+
+/// Read an entire input file (or stdin, given "-") into memory
+pub fn read_input(filename: &str) -> Result<String, Error> {
+    let mut input = String::new();
+    open(filename)?.read_to_string(&mut input)?;
+
+    Ok(input)
+}
+
+/// The contents of an input file, either mapped into memory or read into a
+/// buffer. Derefs to the file's text, so parsers can borrow lines from it
+/// without copying them.
+pub enum Input {
+    Mapped(Mmap),
+    Read(String),
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            // SAFETY: the mapping was checked to be valid UTF-8 when loaded
+            Input::Mapped(map) => unsafe { str::from_utf8_unchecked(map) },
+            Input::Read(s) => s,
+        }
+    }
+}
+
+/// Load an input file, memory-mapping it where possible.
+///
+/// Stdin, compressed and empty files can't be mapped, and are read into
+/// memory as by `read_input`.
+pub fn load_input(filename: &str) -> Result<Input, Error> {
+    let open_error = |source| Error::Open {
+        path: filename.to_string(),
+        source,
+    };
+
+    if filename == "-" {
+        return read_input(filename).map(Input::Read);
+    }
+
+    let file = File::open(filename).map_err(open_error)?;
+    if file.metadata().map_err(open_error)?.len() == 0 {
+        return Ok(Input::Read(String::new()));
+    }
+
+    // SAFETY: the mapping is read-only, and inputs aren't expected to be
+    // modified while a solution is running
+    let map = unsafe { Mmap::map(&file) }.map_err(open_error)?;
+    if map.starts_with(&GZIP_MAGIC) || map.starts_with(&ZSTD_MAGIC) {
+        return read_input(filename).map(Input::Read);
+    }
+
+    str::from_utf8(&map).map_err(|e| open_error(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+
+    Ok(Input::Mapped(map))
+}
+
+/// Wrap a reader in a decoder if its first bytes identify a compressed stream
+fn decompress(mut reader: Box<dyn BufRead>) -> io::Result<Box<dyn BufRead>> {
+    let magic = reader.fill_buf()?;
+
+    if magic.starts_with(&GZIP_MAGIC) {
+        Ok(Box::new(BufReader::new(MultiGzDecoder::new(reader))))
+    } else if magic.starts_with(&ZSTD_MAGIC) {
+        Ok(Box::new(BufReader::new(zstd::Decoder::with_buffer(
+            reader,
+        )?)))
+    } else {
+        Ok(reader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::{Cursor, Write};
+
+    const TEXT: &str = "1000\n2000\n\n3000\n";
+
+    fn read_all(reader: Box<dyn BufRead>) -> String {
+        let mut s = String::new();
+        decompress(reader).unwrap().read_to_string(&mut s).unwrap();
+        s
+    }
+
+    #[test]
+    fn test_decompress_plain() {
+        let reader = Box::new(Cursor::new(TEXT.as_bytes().to_vec()));
+        assert_eq!(read_all(reader), TEXT);
+    }
+
+    #[test]
+    fn test_decompress_gzip() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(TEXT.as_bytes()).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        assert_eq!(read_all(Box::new(Cursor::new(bytes))), TEXT);
+    }
+
+    #[test]
+    fn test_decompress_zstd() {
+        let bytes = zstd::encode_all(TEXT.as_bytes(), 0).unwrap();
+
+        assert_eq!(read_all(Box::new(Cursor::new(bytes))), TEXT);
+    }
+
+    #[test]
+    fn test_open_missing_file() {
+        let err = open("does/not/exist.txt").err().unwrap();
+
+        assert!(matches!(err, Error::Open { .. }));
+        assert!(err.to_string().starts_with("does/not/exist.txt: "));
+    }
+
+    /// Write bytes to a fresh file in the system's temporary directory
+    fn temp_file(name: &str, bytes: &[u8]) -> String {
+        let path = std::env::temp_dir().join(format!("aoc-{}-{}", std::process::id(), name));
+        std::fs::write(&path, bytes).unwrap();
+
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_load_input() {
+        let path = temp_file("plain.txt", TEXT.as_bytes());
+        let input = load_input(&path).unwrap();
+        assert!(matches!(input, Input::Mapped(_)));
+        assert_eq!(&*input, TEXT);
+
+        let path = temp_file("input.zst", &zstd::encode_all(TEXT.as_bytes(), 0).unwrap());
+        let input = load_input(&path).unwrap();
+        assert!(matches!(input, Input::Read(_)));
+        assert_eq!(&*input, TEXT);
+
+        let path = temp_file("empty.txt", b"");
+        assert_eq!(&*load_input(&path).unwrap(), "");
+
+        let path = temp_file("binary.txt", &[0xff, 0xfe, b'\n']);
+        assert!(matches!(load_input(&path), Err(Error::Open { .. })));
+    }
+}
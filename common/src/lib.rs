@@ -0,0 +1,36 @@
+//! Functionality shared by every day's solution: command-line arguments,
+//! input handling, errors and tracing.
+//!
+//! Reading input files, parsing arguments and printing traces need the `cli`
+//! feature (on by default); without it, only what solvers themselves use is
+//! built, for targets like WebAssembly which have no filesystem. Without the
+//! `std` feature too, the crate needs only `core` and `alloc`, and streaming
+//! solutions (which read from `std::io`) are left out.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "cli")]
+mod args;
+mod error;
+#[cfg(feature = "cli")]
+mod input;
+mod parse;
+mod solution;
+#[cfg(feature = "cli")]
+mod trace;
+
+#[cfg(feature = "cli")]
+pub use args::{get_args, Args, Output};
+pub use error::Error;
+#[cfg(feature = "cli")]
+pub use input::{load_input, open, read_input, Input};
+#[cfg(feature = "std")]
+pub use parse::each_line;
+pub use parse::{parse_lines, ParseError, Skipped};
+pub use solution::Solution;
+#[cfg(feature = "std")]
+pub use solution::Streaming;
+#[cfg(feature = "cli")]
+pub use trace::init_tracing;
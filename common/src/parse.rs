@@ -0,0 +1,262 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+use tracing::debug;
+
+use crate::Error;
+
+/// Malformed puzzle input, located by line and column (both counted from 1).
+///
+/// Line parsers only see the line they are given, so they create errors with
+/// a column and leave the line number to be filled in by their caller with
+/// [`ParseError::with_line`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// Error about `token`, which must be a slice of `line`
+    pub fn at(line: &str, token: &str, reason: impl Into<String>) -> Self {
+        ParseError {
+            line: 0,
+            column: column_of(line, token),
+            text: token.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Error about a line as a whole
+    pub fn line(line: &str, reason: impl Into<String>) -> Self {
+        Self::at(line, line, reason)
+    }
+
+    /// Record the number of the line the error occurred on
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Relocate an error found while parsing `fragment`, a slice of `line`,
+    /// so that its column counts from the start of `line`
+    pub fn within(mut self, line: &str, fragment: &str) -> Self {
+        self.column += column_of(line, fragment) - 1;
+        self
+    }
+}
+
+/// Column (in characters, from 1) at which `token` starts within `line`
+fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|&offset| offset <= line.len() && line.is_char_boundary(offset))
+        .or_else(|| line.find(token))
+        .unwrap_or(0);
+
+    line[..offset].chars().count() + 1
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {} ({:?})",
+            self.line, self.column, self.reason, self.text
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Malformed lines met while parsing.
+///
+/// In strict mode the first malformed line is an error. Otherwise parsers skip
+/// malformed lines, which are recorded here so they can be reported once
+/// parsing is done.
+#[derive(Debug, Default)]
+pub struct Skipped {
+    strict: bool,
+    errors: Vec<ParseError>,
+}
+
+impl Skipped {
+    pub fn new(strict: bool) -> Self {
+        Skipped {
+            strict,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Fail on a malformed line in strict mode, otherwise record it and carry on
+    pub fn skip(&mut self, e: ParseError) -> Result<(), Error> {
+        if self.strict {
+            return Err(e.into());
+        }
+
+        debug!(line = e.line, reason = %e.reason, "skipped malformed line");
+        self.errors.push(e);
+        Ok(())
+    }
+
+    /// The malformed lines skipped so far
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Print a summary of any skipped lines to stderr
+    #[cfg(feature = "std")]
+    pub fn report(&self) {
+        if !self.is_empty() {
+            eprint!("{}", self);
+        }
+    }
+}
+
+impl fmt::Display for Skipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.errors.len() == 1 { "" } else { "s" };
+        writeln!(f, "Skipped {} malformed line{}:", self.errors.len(), plural)?;
+        for e in &self.errors {
+            writeln!(f, "  {}", e)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse each line of an input, numbering any error by the line it occurred on
+/// and skipping malformed lines unless strict
+pub fn parse_lines<'a, T, F>(
+    input: &'a str,
+    skipped: &mut Skipped,
+    mut parse: F,
+) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&'a str) -> Result<T, ParseError>,
+{
+    let mut parsed = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        match parse(line) {
+            Ok(value) => parsed.push(value),
+            Err(e) => skipped.skip(e.with_line(i + 1))?,
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Call `f` with each line of a reader (without its line ending) and the
+/// line's number, counted from 1.
+///
+/// A single buffer is reused for every line, so memory use is bounded by the
+/// longest line rather than the size of the input.
+#[cfg(feature = "std")]
+pub fn each_line<R, F>(mut reader: R, mut f: F) -> Result<(), Error>
+where
+    R: BufRead,
+    F: FnMut(usize, &str) -> Result<(), Error>,
+{
+    let mut buffer = String::new();
+
+    for n in 1.. {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            break;
+        }
+
+        let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        f(n, line.strip_suffix('\r').unwrap_or(line))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_at() {
+        let line = "move 1 from x to 5";
+        let token = line.split_whitespace().nth(3).unwrap();
+        let e = ParseError::at(line, token, "expected a number").with_line(4);
+
+        assert_eq!(e.line, 4);
+        assert_eq!(e.column, 13);
+        assert_eq!(e.text, "x");
+        assert_eq!(
+            e.to_string(),
+            "line 4, column 13: expected a number (\"x\")"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_within() {
+        let line = "2-4,6-x";
+        let fragment = &line[4..];
+        let e =
+            ParseError::at(fragment, &fragment[2..], "expected a number").within(line, fragment);
+
+        assert_eq!(e.column, 7);
+    }
+
+    fn parse_number(line: &str) -> Result<u32, ParseError> {
+        line.parse::<u32>()
+            .map_err(|e| ParseError::line(line, e.to_string()))
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parsed = parse_lines("1\n2\n3", &mut Skipped::new(true), parse_number);
+        assert_eq!(parsed.unwrap(), vec![1, 2, 3]);
+
+        let err = parse_lines("1\nx\n3", &mut Skipped::new(true), parse_number);
+        match err {
+            Err(Error::Parse(e)) => {
+                assert_eq!(e.line, 2);
+                assert_eq!(e.column, 1);
+                assert_eq!(e.text, "x");
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_lines_lenient() {
+        let mut skipped = Skipped::new(false);
+        let parsed = parse_lines("1\nx\n3\n-4", &mut skipped, parse_number);
+
+        assert_eq!(parsed.unwrap(), vec![1, 3]);
+        assert_eq!(skipped.errors().len(), 2);
+        assert_eq!(skipped.errors()[0].line, 2);
+        assert_eq!(skipped.errors()[1].line, 4);
+        assert!(skipped
+            .to_string()
+            .starts_with("Skipped 2 malformed lines:\n"));
+    }
+
+    #[test]
+    fn test_each_line() {
+        let mut lines = Vec::new();
+        each_line("a\nb\r\n\nc".as_bytes(), |n, line| {
+            lines.push((n, line.to_string()));
+            Ok(())
+        })
+        .unwrap();
+
+        let expected = [(1, "a"), (2, "b"), (3, ""), (4, "c")];
+        assert_eq!(lines, expected.map(|(n, l)| (n, l.to_string())));
+    }
+}
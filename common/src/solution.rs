@@ -0,0 +1,49 @@
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+use tracing::debug_span;
+
+use crate::{Error, Skipped};
+
+/// A day's puzzle, split into parsing and solving each of its two parts.
+///
+/// Parsing is kept separate so that callers can time it independently of
+/// the solvers, and solve both parts from a single parsed input.
+pub trait Solution {
+    /// Parsed representation of the puzzle input, which may borrow from it
+    type Input<'a>;
+
+    /// Parse the raw puzzle input, recording malformed lines in `skipped`
+    /// (or failing on them, if it is strict)
+    fn parse<'a>(input: &'a str, skipped: &mut Skipped) -> Result<Self::Input<'a>, Error>;
+
+    /// Solve part 1 of the puzzle, failing if the input turns out to have
+    /// no answer
+    fn part1(input: &Self::Input<'_>) -> Result<impl Display, Error>;
+
+    /// Solve part 2 of the puzzle, failing if the input turns out to have
+    /// no answer
+    fn part2(input: &Self::Input<'_>) -> Result<impl Display, Error>;
+
+    /// Parse the raw puzzle input and solve one of its parts
+    fn solve(input: &str, part2: bool, skipped: &mut Skipped) -> Result<String, Error> {
+        let input = debug_span!("parse").in_scope(|| Self::parse(input, skipped))?;
+
+        let _span = debug_span!("solve", part = if part2 { 2 } else { 1 }).entered();
+        if part2 {
+            Ok(Self::part2(&input)?.to_string())
+        } else {
+            Ok(Self::part1(&input)?.to_string())
+        }
+    }
+}
+
+/// A day whose answers can be computed line by line, dropping each line once
+/// it is read. Memory is bounded by the state the puzzle needs, not by the
+/// number of lines, though some puzzles' state grows as the input goes on.
+#[cfg(feature = "std")]
+pub trait Streaming: Solution {
+    /// Solve both parts of the puzzle in a single pass over the input
+    fn stream<R: BufRead>(reader: R, skipped: &mut Skipped) -> Result<[String; 2], Error>;
+}
@@ -0,0 +1,73 @@
+use std::{
+    fs::File,
+    io::{self, IsTerminal},
+    path::Path,
+    sync::Arc,
+};
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+use crate::Error;
+
+/// Level of events shown for a number of `-v` flags
+fn level(verbose: u8) -> &'static str {
+    match verbose {
+        0 => "warn",
+        1 => "debug",
+        _ => "trace",
+    }
+}
+
+/// Filter from `RUST_LOG` if it is set, otherwise showing events at `level`
+fn filter(level: &str) -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level))
+}
+
+/// Set up tracing of solver internals.
+///
+/// Events are written to stderr at the level chosen by `verbose` (warnings,
+/// then `debug` for -v and `trace` for -vv), or as `RUST_LOG` directs. Given a
+/// `trace` file, events at every level are also written to it as JSON, one
+/// per line. Does nothing if tracing has already been set up.
+pub fn init_tracing(verbose: u8, trace: Option<&Path>) -> Result<(), Error> {
+    let stderr = fmt::layer()
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .with_filter(filter(level(verbose)));
+
+    let json = match trace {
+        Some(path) => {
+            let file = File::create(path).map_err(|source| Error::Open {
+                path: path.display().to_string(),
+                source,
+            })?;
+
+            let layer = fmt::layer()
+                .json()
+                .with_span_list(true)
+                .with_writer(Arc::new(file))
+                .with_filter(EnvFilter::new("trace"));
+            Some(layer)
+        }
+        None => None,
+    };
+
+    let _ = tracing_subscriber::registry()
+        .with(stderr)
+        .with(json)
+        .try_init();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(0), "warn");
+        assert_eq!(level(1), "debug");
+        assert_eq!(level(2), "trace");
+        assert_eq!(level(5), "trace");
+    }
+}
@@ -0,0 +1,63 @@
+//! Integer types to add up calories in, trading memory for how large a total
+//! they can hold
+
+use core::{
+    fmt::{Debug, Display},
+    ops::{Add, Div, Sub},
+};
+
+/// An unsigned integer type holding totals of calories
+pub trait Calories:
+    Clone
+    + Debug
+    + Default
+    + Display
+    + Ord
+    + From<u32>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Div<Output = Self>
+{
+    /// Name of the type, for reporting overflows
+    const NAME: &'static str;
+
+    /// Add two amounts of calories, or `None` if the sum overflows
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// Approximate an amount of calories, for averaging
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_calories {
+    ($($t:ty),*) => {
+        $(
+            impl Calories for $t {
+                const NAME: &'static str = stringify!($t);
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_calories!(u32, u64, u128);
+
+#[cfg(feature = "bigint")]
+impl Calories for num_bigint::BigUint {
+    const NAME: &'static str = "arbitrary precision";
+
+    /// Never overflows
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn to_f64(&self) -> f64 {
+        num_traits::ToPrimitive::to_f64(self).unwrap_or(f64::INFINITY)
+    }
+}
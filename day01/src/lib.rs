@@ -0,0 +1,456 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{collections::BinaryHeap, format, vec::Vec};
+#[cfg(feature = "std")]
+use aoc_common::{each_line, Streaming};
+use aoc_common::{Error, ParseError, Skipped, Solution};
+use core::{cmp::Reverse, fmt::Display};
+#[cfg(feature = "std")]
+use std::{io::BufRead, string::String};
+use tracing::trace;
+
+pub mod calories;
+pub mod stats;
+
+pub use calories::Calories;
+
+/// An elf, the calories of each item in its inventory, and their total
+#[derive(Clone, Debug, PartialEq)]
+pub struct Elf<C> {
+    /// Position of the elf in the input, counting from 0
+    pub index: usize,
+    pub items: Vec<u32>,
+    pub total: C,
+}
+
+/// Collects the items listed on consecutive lines into an elf's inventory
+struct Inventories<C> {
+    n_elves: usize,
+    items: Option<(Vec<u32>, C)>,
+}
+
+impl<C: Calories> Inventories<C> {
+    fn new() -> Self {
+        Inventories {
+            n_elves: 0,
+            items: None,
+        }
+    }
+
+    /// Add line `i` of the input to the current elf's inventory, returning
+    /// the elf once its inventory ends
+    fn add_line(
+        &mut self,
+        i: usize,
+        calories: &str,
+        skipped: &mut Skipped,
+    ) -> Result<Option<Elf<C>>, Error> {
+        // Empty lines denote breaks between "elves"
+        if calories.is_empty() {
+            return Ok(self.finish());
+        }
+
+        // Add calories to current elf, who holds no items until one is valid
+        let (items, total) = self.items.get_or_insert_with(Default::default);
+        let count = calories.trim();
+        match count.parse::<u32>() {
+            Ok(item) => {
+                *total = total.checked_add(&C::from(item)).ok_or_else(|| {
+                    let elf = self.n_elves + 1;
+                    let reason = format!("elf {}'s total calories overflow {}", elf, C::NAME);
+                    ParseError::at(calories, count, reason).with_line(i)
+                })?;
+                items.push(item);
+            }
+            Err(e) => {
+                let reason = format!("invalid calorie count: {}", e);
+                skipped.skip(ParseError::at(calories, count, reason).with_line(i))?;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// End the current elf's inventory, if any lines were listed since the
+    /// last one ended
+    fn finish(&mut self) -> Option<Elf<C>> {
+        let (items, total) = self.items.take()?;
+        let elf = Elf {
+            index: self.n_elves,
+            items,
+            total,
+        };
+        trace!(index = elf.index, total = %elf.total, "elf's inventory");
+        self.n_elves += 1;
+
+        Some(elf)
+    }
+}
+
+/// Parse each elf's inventory from input files, adding up calories in `C`.
+///
+/// Input files are formatted with calories listed on consecutive lines, with
+/// blank lines separating inventories of different elves. The final elf's
+/// inventory may end with the file rather than a blank line.
+///
+/// Unparsable counts are left out of an elf's inventory unless `skipped` is
+/// strict, while a total which overflows `C` is always an error.
+pub fn parse_elves<C: Calories>(input: &str, skipped: &mut Skipped) -> Result<Vec<Elf<C>>, Error> {
+    let mut elves = Vec::new();
+    let mut inventories = Inventories::new();
+
+    for (i, calories) in input.lines().enumerate() {
+        elves.extend(inventories.add_line(i + 1, calories, skipped)?);
+    }
+    elves.extend(inventories.finish());
+
+    Ok(elves)
+}
+
+/// Parse the total calories carried by each elf, in order
+pub fn parse_totals<C: Calories>(input: &str, skipped: &mut Skipped) -> Result<Vec<C>, Error> {
+    let elves = parse_elves(input, skipped)?;
+
+    Ok(elves.into_iter().map(|elf| elf.total).collect())
+}
+
+/// Call `f` with each elf's inventory, in order, as soon as the line ending
+/// it has been read
+#[cfg(feature = "std")]
+pub fn for_each_elf<C, R, F>(reader: R, skipped: &mut Skipped, mut f: F) -> Result<(), Error>
+where
+    C: Calories,
+    R: BufRead,
+    F: FnMut(Elf<C>) -> Result<(), Error>,
+{
+    let mut inventories = Inventories::new();
+
+    each_line(reader, |i, calories| {
+        match inventories.add_line(i, calories, skipped)? {
+            Some(elf) => f(elf),
+            None => Ok(()),
+        }
+    })?;
+
+    inventories.finish().map_or(Ok(()), f)
+}
+
+/// Keeps the `k` elves carrying the most calories out of those pushed so far,
+/// in a min-heap so the lightest load is the one replaced
+pub struct TopK<C> {
+    k: usize,
+    // Ties go to the elf pushed first
+    heap: BinaryHeap<Reverse<(C, Reverse<usize>)>>,
+}
+
+impl<C: Ord> TopK<C> {
+    /// Keep up to `k` elves. The heap grows as elves are pushed, as `k` may
+    /// be far larger than the number of elves.
+    pub fn new(k: usize) -> Self {
+        TopK {
+            k,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Offer the total carried by elf `index`
+    pub fn push(&mut self, index: usize, total: C) {
+        let entry = Reverse((total, Reverse(index)));
+
+        if self.heap.len() < self.k {
+            self.heap.push(entry);
+        } else if let Some(mut lightest) = self.heap.peek_mut() {
+            // Heavier loads order first under `Reverse`
+            if entry < *lightest {
+                *lightest = entry;
+            }
+        }
+    }
+
+    /// Elves kept so far as `(index, total)`, from the heaviest load down
+    pub fn into_sorted_vec(self) -> Vec<(usize, C)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| (index, total))
+            .collect()
+    }
+}
+
+/// Report the `k` elves carrying the most calories as `(index, total)`, from
+/// the heaviest load down, and with ties going to the earlier elf. When there
+/// are fewer than `k` elves, all of them are reported.
+pub fn top_k<C: Ord + Clone>(totals: &[C], k: usize) -> Vec<(usize, C)> {
+    let mut top = TopK::new(k);
+    for (i, total) in totals.iter().enumerate() {
+        top.push(i, total.clone());
+    }
+
+    top.into_sorted_vec()
+}
+
+/// The `k` elves carrying the most calories out of those seen so far, kept in
+/// order as each elf arrives
+pub struct Leaderboard<C> {
+    k: usize,
+    leaders: Vec<(usize, C)>,
+}
+
+impl<C: Ord> Leaderboard<C> {
+    /// Keep up to `k` leaders, which may be far more than there are elves
+    pub fn new(k: usize) -> Self {
+        Leaderboard {
+            k,
+            leaders: Vec::new(),
+        }
+    }
+
+    /// Add the total carried by elf `index`, reporting whether it made the
+    /// leaderboard. Ties go to the elf which arrived first.
+    pub fn push(&mut self, index: usize, total: C) -> bool {
+        let rank = self.leaders.partition_point(|(_, leader)| *leader >= total);
+        if rank >= self.k {
+            return false;
+        }
+
+        self.leaders.insert(rank, (index, total));
+        self.leaders.truncate(self.k);
+        true
+    }
+
+    /// Elves on the leaderboard as `(index, total)`, from the heaviest load
+    /// down
+    pub fn leaders(&self) -> &[(usize, C)] {
+        &self.leaders
+    }
+}
+
+/// Add up the totals of some elves, or `None` if the sum overflows
+pub fn sum_totals<'a, C: Calories + 'a>(totals: impl IntoIterator<Item = &'a C>) -> Option<C> {
+    totals
+        .into_iter()
+        .try_fold(C::default(), |sum, total| sum.checked_add(total))
+}
+
+/// Report the maximum calories carried by a single elf, or 0 if there are no
+/// elves
+pub fn max_total(totals: &[u64]) -> u64 {
+    totals.iter().copied().max().unwrap_or(0)
+}
+
+/// Report the total calories carried by the elves with the three highest
+/// loads, widened so that the sum cannot overflow
+pub fn top3_total(totals: &[u64]) -> u128 {
+    top_k(totals, 3)
+        .iter()
+        .map(|&(_, total)| u128::from(total))
+        .sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    /// Total calories carried by each elf, with room for far larger
+    /// inventories than the puzzle's
+    type Input<'a> = Vec<u64>;
+
+    fn parse<'a>(input: &'a str, skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        parse_totals(input, skipped)
+    }
+
+    fn part1(totals: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(max_total(totals))
+    }
+
+    fn part2(totals: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(top3_total(totals))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Streaming for Day1 {
+    /// Keep only the three highest totals seen so far
+    fn stream<R: BufRead>(reader: R, skipped: &mut Skipped) -> Result<[String; 2], Error> {
+        let mut top = TopK::new(3);
+        for_each_elf(reader, skipped, |elf: Elf<u64>| {
+            top.push(elf.index, elf.total);
+            Ok(())
+        })?;
+
+        let top = top.into_sorted_vec();
+        let max = top.first().map_or(0, |&(_, total)| total);
+        let sum: u128 = top.iter().map(|&(_, total)| u128::from(total)).sum();
+
+        Ok([max.to_string(), sum.to_string()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Inventories of a few elves, each carrying at least one item
+    fn inventories() -> impl Strategy<Value = Vec<Vec<u32>>> {
+        prop::collection::vec(prop::collection::vec(0..100_000u32, 1..10), 0..20)
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = "1000\n2000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n\n";
+        let answers = Day1::stream(input.as_bytes(), &mut Skipped::new(true)).unwrap();
+
+        assert_eq!(answers, ["24000", "45000"]);
+    }
+
+    #[test]
+    fn test_parse_elves() {
+        let input = "1000\n2000\n\n\n4000\nx\n\n5000";
+        let mut skipped = Skipped::new(false);
+        let elves = parse_elves::<u32>(input, &mut skipped).unwrap();
+
+        assert_eq!(
+            elves,
+            [
+                Elf {
+                    index: 0,
+                    items: vec![1000, 2000],
+                    total: 3000
+                },
+                Elf {
+                    index: 1,
+                    items: vec![4000],
+                    total: 4000
+                },
+                Elf {
+                    index: 2,
+                    items: vec![5000],
+                    total: 5000
+                },
+            ]
+        );
+        assert_eq!(skipped.errors()[0].line, 6);
+
+        let answers = Day1::stream(input.as_bytes(), &mut Skipped::new(false)).unwrap();
+        assert_eq!(answers, ["5000", "12000"]);
+    }
+
+    #[test]
+    fn test_top_k() {
+        let totals = [6000, 4000, 11000, 24000, 10000, 11000];
+
+        assert_eq!(top_k(&totals, 3), [(3, 24000), (2, 11000), (5, 11000)]);
+        assert_eq!(top_k(&totals[..2], 3), [(0, 6000), (1, 4000)]);
+        assert!(top_k(&totals, 0).is_empty());
+        assert_eq!(top_k(&totals, usize::MAX).len(), totals.len());
+
+        assert_eq!(max_total(&[]), 0);
+        assert_eq!(top3_total(&[6000, 4000]), 10000);
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let input = "1000\n2000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n1000\n\n11000";
+        let mut leaderboard = Leaderboard::new(3);
+        let mut changes = Vec::new();
+
+        for_each_elf(
+            input.as_bytes(),
+            &mut Skipped::new(true),
+            |elf: Elf<u32>| {
+                if leaderboard.push(elf.index, elf.total) {
+                    changes.push(elf.index);
+                }
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        // The elf carrying 1000 calories never makes the leaderboard
+        assert_eq!(changes, [0, 1, 2, 3, 5]);
+        assert_eq!(leaderboard.leaders(), [(3, 24000), (2, 11000), (5, 11000)]);
+
+        let mut everyone = Leaderboard::new(usize::MAX);
+        assert!(everyone.push(0, 1000));
+        assert!(everyone.push(1, 2000));
+        assert_eq!(everyone.leaders(), [(1, 2000), (0, 1000)]);
+
+        let mut empty = Leaderboard::new(0);
+        assert!(!empty.push(0, 1));
+        assert!(empty.leaders().is_empty());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = "4294967295\n\n4294967295\n1\n\n3";
+
+        match parse_totals::<u32>(input, &mut Skipped::new(false)) {
+            Err(Error::Parse(e)) => {
+                assert_eq!(e.line, 4);
+                assert_eq!(e.reason, "elf 2's total calories overflow u32");
+            }
+            _ => panic!("expected an overflow"),
+        }
+
+        let totals = parse_totals::<u64>(input, &mut Skipped::new(true)).unwrap();
+        assert_eq!(totals, [4294967295, 4294967296, 3]);
+        assert_eq!(top3_total(&totals), 8589934594);
+        assert_eq!(
+            parse_totals::<u128>(input, &mut Skipped::new(true)).unwrap()[1],
+            4294967296
+        );
+
+        let totals = parse_totals::<u32>("4294967295\n\n1", &mut Skipped::new(true)).unwrap();
+        assert_eq!(sum_totals(&totals), None);
+        assert_eq!(sum_totals(&totals[..1]), Some(u32::MAX));
+        let wide: Vec<u64> = totals.iter().map(|&total| u64::from(total)).collect();
+        assert_eq!(sum_totals(&wide), Some(4294967296));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_bigint() {
+        use num_bigint::BigUint;
+
+        let input = "4294967295\n4294967295\n\n1";
+        let totals = parse_totals::<BigUint>(input, &mut Skipped::new(true)).unwrap();
+
+        assert_eq!(totals, [BigUint::from(8589934590u64), BigUint::from(1u32)]);
+        assert_eq!(sum_totals(&totals), Some(BigUint::from(8589934591u64)));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_totals_never_panics(input in "\\PC*(\n\\PC*)*") {
+            let _ = parse_totals::<u32>(&input, &mut Skipped::new(false));
+        }
+
+        #[test]
+        fn parse_totals_round_trips(elves in inventories()) {
+            // The final elf's inventory ends with the input
+            let input = elves
+                .iter()
+                .map(|items| {
+                    let lines: Vec<String> = items.iter().map(|n| n.to_string()).collect();
+                    lines.join("\n")
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let totals = parse_totals::<u32>(&input, &mut Skipped::new(true)).unwrap();
+
+            let expected: Vec<u32> = elves.iter().map(|items| items.iter().sum()).collect();
+            prop_assert_eq!(totals, expected);
+        }
+
+        #[test]
+        fn top_k_matches_sorting(totals in prop::collection::vec(0..10u32, 0..50), k in 0..10usize) {
+            let mut sorted: Vec<(usize, u32)> = totals.iter().copied().enumerate().collect();
+            sorted.sort_by_key(|&(i, total)| (Reverse(total), i));
+            sorted.truncate(k);
+
+            prop_assert_eq!(top_k(&totals, k), sorted);
+        }
+    }
+}
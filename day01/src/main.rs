@@ -1,57 +1,237 @@
-use std::io;
-use std::io::BufRead;
-// use std::io::BufReader;
-
-/// Parse calorie totals from input files.
-///
-/// Input files are formatted with calories listed on consecutive lines, with
-/// blank lines separating inventories of different elves.
-///
-/// The total calories carried by each elf are stored and returned in a vector.
-///
-fn parse_totals<R: BufRead>(reader: &mut R) -> Vec<u32> {
-    let mut totals: Vec<u32> = Vec::new();
-    let mut curr_total: u32 = 0;
-
-    for line in reader.lines() {
-        let calories = line.expect("Failed to read");
-
-        // Empty lines denote breaks between "elves"
-        if calories.is_empty() {
-            totals.push(curr_total);
-            curr_total = 0;
-        // Add calories to current elf
-        } else {
-            let calories: u32 = match calories.trim().parse() {
-                Ok(num) => num,
-                Err(_) => continue,
-            };
-            curr_total += calories;
-        }
+use aoc_common::{init_tracing, load_input, open, Args, Error, Output, Skipped};
+use clap::{Parser, ValueEnum};
+use day1::{
+    for_each_elf, parse_elves, parse_totals, stats::Stats, sum_totals, top_k, Calories, Elf,
+    Leaderboard,
+};
+use serde::Serialize;
+use serde_json::value::RawValue;
+use std::fmt::Display;
+
+/// Integer types to add up calories in
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Width {
+    U32,
+    U64,
+    U128,
+    /// Arbitrary precision, which never overflows
+    Big,
+}
+
+/// Count the calories carried by each elf. Takes the standard arguments, plus
+/// queries only this day answers.
+#[derive(Parser, Debug)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+
+    #[arg(
+        long = "top",
+        id = "K",
+        help = "Report the K elves carrying the most calories, and their total (3 when following)",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    top: Option<u64>,
+
+    #[arg(
+        long = "stats",
+        help = "Report statistics of the calories carried",
+        conflicts_with = "K"
+    )]
+    stats: bool,
+
+    #[arg(
+        long = "follow",
+        help = "Report the leaders each time they change, as inventories are read",
+        conflicts_with = "stats"
+    )]
+    follow: bool,
+
+    #[arg(
+        long = "output",
+        value_enum,
+        help = "Format to report changes of leader in",
+        default_value_t = Output::Text,
+        requires = "follow"
+    )]
+    output: Output,
+
+    #[arg(
+        long = "calories",
+        value_enum,
+        help = "Integer type to add up calories in",
+        default_value_t = Width::U64
+    )]
+    calories: Width,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let result =
+        init_tracing(cli.args.verbose, cli.args.trace.as_deref()).and_then(|_| dispatch(cli));
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
     }
+}
 
-    totals
+/// Run with calories added up in the integer type chosen
+fn dispatch(cli: Cli) -> Result<(), Error> {
+    match cli.calories {
+        Width::U32 => run::<u32>(cli),
+        Width::U64 => run::<u64>(cli),
+        Width::U128 => run::<u128>(cli),
+        #[cfg(feature = "bigint")]
+        Width::Big => run::<num_bigint::BigUint>(cli),
+        #[cfg(not(feature = "bigint"))]
+        Width::Big => Err(Error::Unsupported {
+            day: 1,
+            feature: "arbitrary precision calories",
+        }),
+    }
 }
 
-/// Report the maximum calories carried by a single elf
-/// (aka unpack and dereference the max of a Vector)
-fn max_total(totals: &Vec<u32>) -> u32 {
-    *totals.iter().max().unwrap()
+/// Add up the totals of the `k` heaviest loads
+fn top_total<C: Calories>(totals: &[C], k: usize) -> Result<(Vec<(usize, C)>, C), Error> {
+    let top = top_k(totals, k);
+    let sum = sum_totals(top.iter().map(|(_, total)| total)).ok_or(Error::Overflow {
+        day: 1,
+        width: C::NAME,
+    })?;
+
+    Ok((top, sum))
 }
 
-/// Report the total calories carried by the elves with the three highest loads
-fn top3_total(totals: &mut Vec<u32>) -> u32 {
-    totals.sort();
-    totals.reverse();
+/// An elf among the leaders, as reported by `--output json`
+#[derive(Serialize)]
+struct JsonLeader {
+    elf: usize,
+    total: Box<RawValue>,
+}
 
-    totals[..3].iter().sum()
+/// A change of leaders, as reported by `--output json`
+#[derive(Serialize)]
+struct JsonEvent {
+    elf: usize,
+    leader: usize,
+    top: Vec<JsonLeader>,
+    top_total: Box<RawValue>,
 }
 
-fn main() {
-    // let mut reader = BufReader::new(io::stdin());
-    let mut reader = io::stdin().lock(); // StdinLock implements BufRead
-    let mut totals = parse_totals(&mut reader);
+/// A total of calories as a JSON number, however wide its type
+fn json_number(total: &impl Display) -> Box<RawValue> {
+    RawValue::from_string(total.to_string()).expect("totals are JSON numbers")
+}
+
+/// Describe the leaders after elf `index` arrives, as a line of text or JSON.
+/// There is always at least one leader once an elf has arrived.
+fn leaders_event<C: Calories>(
+    index: usize,
+    leaders: &[(usize, C)],
+    output: Output,
+) -> Result<String, Error> {
+    let sum = sum_totals(leaders.iter().map(|(_, total)| total)).ok_or(Error::Overflow {
+        day: 1,
+        width: C::NAME,
+    })?;
+
+    // Elves are numbered from 1, in the order of the input
+    let leader = leaders[0].0 + 1;
+    let event = match output {
+        Output::Json => {
+            let event = JsonEvent {
+                elf: index + 1,
+                leader,
+                top: leaders
+                    .iter()
+                    .map(|(i, total)| JsonLeader {
+                        elf: i + 1,
+                        total: json_number(total),
+                    })
+                    .collect(),
+                top_total: json_number(&sum),
+            };
+            serde_json::to_string(&event).expect("events serialize to JSON")
+        }
+        Output::Text => {
+            let top: Vec<String> = leaders
+                .iter()
+                .map(|(i, total)| format!("elf {} ({})", i + 1, total))
+                .collect();
+            format!(
+                "After elf {}: elf {} leads, top {} carry {}: {}",
+                index + 1,
+                leader,
+                leaders.len(),
+                sum,
+                top.join(", ")
+            )
+        }
+    };
+
+    Ok(event)
+}
+
+/// Read inventories as they arrive, reporting the leaders whenever they change
+fn follow<C: Calories>(
+    fin: &str,
+    k: usize,
+    output: Output,
+    skipped: &mut Skipped,
+) -> Result<(), Error> {
+    let mut leaderboard = Leaderboard::new(k);
+
+    for_each_elf(open(fin)?, skipped, |elf: Elf<C>| {
+        if leaderboard.push(elf.index, elf.total) {
+            println!(
+                "{}",
+                leaders_event(elf.index, leaderboard.leaders(), output)?
+            );
+        }
+
+        Ok(())
+    })
+}
+
+fn run<C: Calories>(cli: Cli) -> Result<(), Error> {
+    let args = cli.args;
+    let mut skipped = Skipped::new(args.strict);
+
+    if cli.follow {
+        let k = cli.top.unwrap_or(3) as usize;
+        follow::<C>(&args.fin, k, cli.output, &mut skipped)?;
+        skipped.report();
+
+        return Ok(());
+    }
+
+    let input = load_input(&args.fin)?;
+    if let Some(k) = cli.top {
+        let totals = parse_totals::<C>(&input, &mut skipped)?;
+        let (top, sum) = top_total(&totals, k as usize)?;
+
+        // Elves are numbered from 1, in the order of the input
+        for (i, total) in &top {
+            println!("Elf {}: {}", i + 1, total);
+        }
+        println!("Top {}: {}", top.len(), sum);
+    } else if cli.stats {
+        match Stats::new(&parse_elves::<C>(&input, &mut skipped)?) {
+            Some(stats) => print!("{}", stats),
+            None => println!("Elves: 0"),
+        }
+    } else {
+        let totals = parse_totals::<C>(&input, &mut skipped)?;
+
+        if args.part2 {
+            println!("Part 2: {}", top_total(&totals, 3)?.1);
+        } else {
+            println!("Part 1: {}", top_total(&totals, 1)?.1);
+        }
+    }
+
+    skipped.report();
 
-    println!("Part 1: {}", max_total(&totals));
-    println!("Part 2: {}", top3_total(&mut totals));
+    Ok(())
 }
@@ -0,0 +1,227 @@
+//! Summary statistics of the calories the elves carry, for planning how much
+//! food an expedition can hold
+
+use crate::{Calories, Elf};
+use alloc::{string::ToString, vec::Vec};
+use core::{
+    cmp::Reverse,
+    fmt::{self, Display},
+};
+
+/// Percentiles of the totals to report
+pub const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// Number of equal-width bins in the histogram of totals
+const HISTOGRAM_BINS: u32 = 10;
+
+/// Length of the bar drawn for the most common bin
+const BAR_WIDTH: usize = 40;
+
+/// Count of the elves whose totals fall between `start` and `end`, inclusive
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bin<C> {
+    pub start: C,
+    pub end: C,
+    pub count: usize,
+}
+
+/// Statistics of the calories carried by at least one elf
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats<C> {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Total at each of [`PERCENTILES`], by the nearest-rank method
+    pub percentiles: Vec<(u8, C)>,
+    pub histogram: Vec<Bin<C>>,
+    /// Largest single item as `(elf index, calories)`, if any elf carries one
+    pub largest_item: Option<(usize, u32)>,
+}
+
+impl<C: Calories> Stats<C> {
+    /// Summarise the elves' inventories, or `None` if there are no elves
+    pub fn new(elves: &[Elf<C>]) -> Option<Stats<C>> {
+        let mut totals: Vec<C> = elves.iter().map(|elf| elf.total.clone()).collect();
+        totals.sort_unstable();
+        let count = totals.len();
+        if count == 0 {
+            return None;
+        }
+
+        // Averages are approximate, so that they cannot overflow
+        let sum: f64 = totals.iter().map(C::to_f64).sum();
+        let middle = (&totals[(count - 1) / 2], &totals[count / 2]);
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&p| (p, percentile(&totals, p)))
+            .collect();
+
+        // Ties go to the first elf carrying the item
+        let largest_item = elves
+            .iter()
+            .flat_map(|elf| elf.items.iter().map(|&item| (elf.index, item)))
+            .max_by_key(|&(index, item)| (item, Reverse(index)));
+
+        Some(Stats {
+            count,
+            mean: sum / count as f64,
+            median: (middle.0.to_f64() + middle.1.to_f64()) / 2.0,
+            percentiles,
+            histogram: histogram(&totals),
+            largest_item,
+        })
+    }
+}
+
+/// Smallest total which at least `p` percent of the sorted totals are no
+/// larger than
+fn percentile<C: Clone>(sorted: &[C], p: u8) -> C {
+    let rank = (usize::from(p) * sorted.len()).div_ceil(100).max(1);
+
+    sorted[rank - 1].clone()
+}
+
+/// Split the range of the sorted totals into equal-width bins, and count the
+/// totals in each
+fn histogram<C: Calories>(sorted: &[C]) -> Vec<Bin<C>> {
+    let (min, max) = (&sorted[0], &sorted[sorted.len() - 1]);
+    let one = || C::from(1);
+    let width = (max.clone() - min.clone()) / C::from(HISTOGRAM_BINS) + one();
+
+    let mut bins = Vec::new();
+    let mut start = min.clone();
+    loop {
+        // The last bin stops at the largest total, so bounds never overflow
+        let end = if max.clone() - start.clone() < width {
+            max.clone()
+        } else {
+            start.clone() + width.clone() - one()
+        };
+        bins.push(Bin {
+            start,
+            end: end.clone(),
+            count: 0,
+        });
+
+        if end == *max {
+            break;
+        }
+        start = end + one();
+    }
+
+    let mut bin = 0;
+    for total in sorted {
+        while *total > bins[bin].end {
+            bin += 1;
+        }
+        bins[bin].count += 1;
+    }
+
+    bins
+}
+
+impl<C: Calories> Display for Stats<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elves: {}", self.count)?;
+        writeln!(f, "Mean: {:.1}", self.mean)?;
+        writeln!(f, "Median: {:.1}", self.median)?;
+        for (p, total) in &self.percentiles {
+            writeln!(f, "{}th percentile: {}", p, total)?;
+        }
+        match self.largest_item {
+            // Elves are numbered from 1, in the order of the input
+            Some((index, item)) => writeln!(f, "Largest item: {} (elf {})", item, index + 1)?,
+            None => writeln!(f, "Largest item: none")?,
+        }
+
+        writeln!(f, "Histogram of totals:")?;
+        let digits = self
+            .histogram
+            .last()
+            .map_or(1, |bin| bin.end.to_string().len());
+        let most = self
+            .histogram
+            .iter()
+            .map(|bin| bin.count)
+            .max()
+            .unwrap_or(0);
+        for bin in &self.histogram {
+            let bar = (bin.count * BAR_WIDTH).div_ceil(most.max(1));
+            writeln!(
+                f,
+                "  {:>digits$}-{:>digits$} {:#<bar$} {}",
+                bin.start, bin.end, "", bin.count
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elves(totals: &[u32]) -> Vec<Elf<u64>> {
+        totals
+            .iter()
+            .enumerate()
+            .map(|(index, &total)| Elf {
+                index,
+                items: vec![total / 2, total - total / 2],
+                total: u64::from(total),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(&elves(&[6000, 4000, 11000, 24000, 10000])).unwrap();
+
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert_eq!(stats.percentiles[0], (10, 4000));
+        assert_eq!(stats.percentiles[4], (99, 24000));
+        assert_eq!(stats.largest_item, Some((3, 12000)));
+
+        assert_eq!(stats.histogram.len(), 10);
+        assert_eq!(
+            stats.histogram[0],
+            Bin {
+                start: 4000,
+                end: 6000,
+                count: 2
+            }
+        );
+        assert_eq!(
+            stats.histogram.iter().map(|bin| bin.count).sum::<usize>(),
+            5
+        );
+
+        let stats = Stats::new(&elves(&[1, 3])).unwrap();
+        assert_eq!(stats.median, 2.0);
+        assert_eq!(stats.histogram.len(), 3);
+
+        assert_eq!(Stats::<u64>::new(&[]), None);
+
+        let extremes = [0, u32::MAX].map(|total| Elf {
+            index: 0,
+            items: vec![total],
+            total,
+        });
+        let stats = Stats::new(&extremes).unwrap();
+        assert_eq!(stats.histogram.len(), 10);
+        assert_eq!(stats.histogram[9].end, u32::MAX);
+
+        let stats = Stats::new(&elves(&[7, 7, 7])).unwrap();
+        assert_eq!(
+            stats.histogram,
+            [Bin {
+                start: 7,
+                end: 7,
+                count: 3
+            }]
+        );
+    }
+}
@@ -0,0 +1,407 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use aoc_common::{each_line, Streaming};
+use aoc_common::{parse_lines, Error, ParseError, Skipped, Solution};
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::{io::BufRead, string::String};
+use tracing::trace;
+
+pub mod rules;
+
+pub use rules::{Column, Rules};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    pub fn score(&self) -> u32 {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Draw => 3,
+            Outcome::Loss => 0,
+        }
+    }
+
+    fn from_move(move_str: &str) -> Option<Self> {
+        match move_str {
+            "X" => Some(Outcome::Loss),
+            "Y" => Some(Outcome::Draw),
+            "Z" => Some(Outcome::Win),
+            &_ => None,
+        }
+    }
+}
+
+/// The shapes thrown by each player, numbered as in some [`Rules`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Round {
+    opponent: usize,
+    response: usize,
+}
+
+impl Round {
+    /// A round between shapes of the rules, or `None` if either is not one
+    pub fn new(rules: &Rules, opponent: usize, response: usize) -> Option<Self> {
+        let n = rules.n_shapes();
+
+        (opponent < n && response < n).then_some(Self { opponent, response })
+    }
+
+    /// Respond to the opponent's shape so as to get the outcome
+    pub fn from_outcome(rules: &Rules, opponent: usize, outcome: Outcome) -> Option<Self> {
+        let response = rules.response(opponent, outcome)?;
+
+        Some(Self { opponent, response })
+    }
+
+    pub fn opponent(&self) -> usize {
+        self.opponent
+    }
+
+    pub fn response(&self) -> usize {
+        self.response
+    }
+
+    /// Outcome under the rules, or `None` if they lack either shape
+    pub fn outcome(&self, rules: &Rules) -> Option<Outcome> {
+        rules.outcome(self.response, self.opponent)
+    }
+
+    /// Score under the rules, or `None` if they lack either shape
+    pub fn score(&self, rules: &Rules) -> Option<u32> {
+        Some(rules.score(self.response)? + self.outcome(rules)?.score())
+    }
+}
+
+/// Parse a line of the strategy guide: the opponent's shape, then either our
+/// response (part 1) or the outcome we need (part 2). Shapes are coded as the
+/// rules decode each [`Column`].
+pub fn parse_round(rules: &Rules, line: &str, is_part_one: bool) -> Result<Round, ParseError> {
+    // parse line into Shapes
+    let moves: Vec<&str> = line.split(' ').collect();
+    if moves.len() != 2 {
+        return Err(ParseError::line(line, "expected two moves"));
+    }
+
+    let opponent = rules
+        .decode(Column::Opponent, moves[0])
+        .ok_or_else(|| ParseError::at(line, moves[0], "unknown shape"))?;
+
+    let round = if is_part_one {
+        let response = rules
+            .decode(Column::Response, moves[1])
+            .ok_or_else(|| ParseError::at(line, moves[1], "unknown shape"))?;
+
+        Round { opponent, response }
+    } else {
+        let outcome = Outcome::from_move(moves[1])
+            .ok_or_else(|| ParseError::at(line, moves[1], "unknown outcome"))?;
+
+        Round::from_outcome(rules, opponent, outcome).expect("valid rules reach every outcome")
+    };
+
+    Ok(round)
+}
+
+/// The strategy guide, read both ways: in part 1 the second column is our
+/// response, and in part 2 it is the outcome we need.
+pub struct StrategyGuide {
+    rules: Rules,
+    as_responses: Vec<Round>,
+    as_outcomes: Vec<Round>,
+}
+
+impl StrategyGuide {
+    /// Rules the guide was parsed under
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn as_responses(&self) -> &[Round] {
+        &self.as_responses
+    }
+
+    pub fn as_outcomes(&self) -> &[Round] {
+        &self.as_outcomes
+    }
+
+    /// Total score if the second column is our response
+    pub fn score_responses(&self) -> u64 {
+        total_score(&self.rules, &self.as_responses)
+    }
+
+    /// Total score if the second column is the outcome we need
+    pub fn score_outcomes(&self) -> u64 {
+        total_score(&self.rules, &self.as_outcomes)
+    }
+}
+
+/// Parse the strategy guide under the rules, skipping lines which cannot be
+/// read both ways
+pub fn parse_guide(
+    rules: Rules,
+    input: &str,
+    skipped: &mut Skipped,
+) -> Result<StrategyGuide, Error> {
+    let (as_responses, as_outcomes) =
+        parse_lines(input, skipped, |line| parse_both_ways(&rules, line))?
+            .into_iter()
+            .unzip();
+
+    Ok(StrategyGuide {
+        rules,
+        as_responses,
+        as_outcomes,
+    })
+}
+
+/// Read a round of the strategy guide both ways
+fn parse_both_ways(rules: &Rules, line: &str) -> Result<(Round, Round), ParseError> {
+    Ok((
+        parse_round(rules, line, true)?,
+        parse_round(rules, line, false)?,
+    ))
+}
+
+/// Call `f` with each round of the strategy guide, read both ways
+#[cfg(feature = "std")]
+fn for_each_round<R, F>(
+    rules: &Rules,
+    reader: R,
+    skipped: &mut Skipped,
+    mut f: F,
+) -> Result<(), Error>
+where
+    R: BufRead,
+    F: FnMut(Round, Round),
+{
+    each_line(reader, |i, line| {
+        match parse_both_ways(rules, line) {
+            Ok((as_response, as_outcome)) => f(as_response, as_outcome),
+            Err(e) => skipped.skip(e.with_line(i))?,
+        }
+
+        Ok(())
+    })
+}
+
+/// Total scores of the strategy guide under the rules, read both ways, as
+/// its lines are read
+#[cfg(feature = "std")]
+pub fn score_stream<R: BufRead>(
+    rules: &Rules,
+    reader: R,
+    skipped: &mut Skipped,
+) -> Result<[u64; 2], Error> {
+    let mut scores = [0, 0];
+    for_each_round(rules, reader, skipped, |as_response, as_outcome| {
+        scores[0] += u64::from(score(rules, &as_response));
+        scores[1] += u64::from(score(rules, &as_outcome));
+    })?;
+
+    Ok(scores)
+}
+
+/// Score of a round parsed under the rules, which have both its shapes
+fn score(rules: &Rules, round: &Round) -> u32 {
+    round
+        .score(rules)
+        .expect("rounds are parsed under the rules they are scored by")
+}
+
+/// Total score of the rounds, in a `u64` as a long guide could overflow a `u32`
+fn total_score(rules: &Rules, rounds: &[Round]) -> u64 {
+    rounds
+        .iter()
+        .map(|round| {
+            let score = score(rules, round);
+            trace!(
+                opponent = rules.name(round.opponent),
+                response = rules.name(round.response),
+                score,
+                "round"
+            );
+            u64::from(score)
+        })
+        .sum()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = StrategyGuide;
+
+    fn parse<'a>(input: &'a str, skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        parse_guide(Rules::rock_paper_scissors(), input, skipped)
+    }
+
+    fn part1(guide: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(guide.score_responses())
+    }
+
+    fn part2(guide: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(guide.score_outcomes())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Streaming for Day2 {
+    fn stream<R: BufRead>(reader: R, skipped: &mut Skipped) -> Result<[String; 2], Error> {
+        let scores = score_stream(&Rules::rock_paper_scissors(), reader, skipped)?;
+
+        Ok(scores.map(|score| score.to_string()))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::identity_op)] // scores are written as shape + outcome
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Throw `response` against `opponent` in the puzzle's rules
+    fn check(response: &str, opponent: &str, outcome: Outcome, score: u32) {
+        let rules = Rules::rock_paper_scissors();
+        let round = Round::new(
+            &rules,
+            rules.shape(opponent).unwrap(),
+            rules.shape(response).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(round.outcome(&rules), Some(outcome));
+        assert_eq!(round.score(&rules), Some(score));
+        assert_eq!(
+            Round::from_outcome(&rules, round.opponent(), outcome),
+            Some(round)
+        );
+    }
+
+    #[test]
+    fn test_throw_paper() {
+        check("Paper", "Rock", Outcome::Win, 2 + 6);
+        check("Paper", "Paper", Outcome::Draw, 2 + 3);
+        check("Paper", "Scissors", Outcome::Loss, 2 + 0);
+    }
+
+    #[test]
+    fn test_throw_rock() {
+        check("Rock", "Rock", Outcome::Draw, 1 + 3);
+        check("Rock", "Paper", Outcome::Loss, 1 + 0);
+        check("Rock", "Scissors", Outcome::Win, 1 + 6);
+    }
+
+    #[test]
+    fn test_throw_scissors() {
+        check("Scissors", "Rock", Outcome::Loss, 3 + 0);
+        check("Scissors", "Paper", Outcome::Win, 3 + 6);
+        check("Scissors", "Scissors", Outcome::Draw, 3 + 3);
+    }
+
+    #[test]
+    fn test_parse_round() {
+        let rules = Rules::rock_paper_scissors();
+
+        let round = parse_round(&rules, "A Y", true).unwrap();
+        assert_eq!(rules.name(round.opponent()), Some("Rock"));
+        assert_eq!(rules.name(round.response()), Some("Paper"));
+
+        let round = parse_round(&rules, "A Y", false).unwrap();
+        assert_eq!(rules.name(round.response()), Some("Rock"));
+
+        let err = parse_round(&rules, "D Y", true).err().unwrap();
+        assert_eq!(err.text, "D");
+
+        let err = parse_round(&rules, "A", true).err().unwrap();
+        assert_eq!(err.column, 1);
+
+        let err = parse_round(&rules, "A Q", false).err().unwrap();
+        assert_eq!(err.column, 3);
+        assert_eq!(err.text, "Q");
+    }
+
+    #[test]
+    fn test_house_rules() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let shape = |name| rules.shape(name).unwrap();
+
+        let round = Round::from_outcome(&rules, shape("Spock"), Outcome::Win).unwrap();
+        assert_eq!(rules.name(round.response()), Some("Paper"));
+        assert_eq!(round.score(&rules), Some(3 + 6));
+
+        let round = Round::new(&rules, shape("Lizard"), shape("Scissors")).unwrap();
+        assert_eq!(round.score(&rules), Some(5 + 6));
+
+        // Scissors are not a shape of the classic rules
+        let classic = Rules::rock_paper_scissors();
+        assert_eq!(round.score(&classic), None);
+        assert_eq!(Round::new(&classic, 0, shape("Scissors")), None);
+        assert_eq!(Round::from_outcome(&classic, 4, Outcome::Win), None);
+
+        // Spock beaten by paper, then scissors by rock and lost to with paper
+        let mut skipped = Skipped::new(false);
+        let guide = parse_guide(rules, "B Z\nE X\nF X\n", &mut skipped).unwrap();
+        assert_eq!(guide.as_responses().len(), 2);
+        assert_eq!(guide.score_responses(), (3 + 6) + (1 + 6));
+        assert_eq!(guide.score_outcomes(), (3 + 6) + (3 + 0));
+        assert_eq!(skipped.errors().len(), 1);
+        assert_eq!(skipped.errors()[0].text, "F");
+
+        let guide = parse_guide(classic, "B Z\nE X\n", &mut Skipped::new(false)).unwrap();
+        assert_eq!(guide.as_responses().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_guide_lenient() {
+        let mut skipped = Skipped::new(false);
+        let guide = parse_guide(
+            Rules::rock_paper_scissors(),
+            "A Y\nB Q\nC Z\n",
+            &mut skipped,
+        )
+        .unwrap();
+
+        assert_eq!(guide.as_responses().len(), 2);
+        assert_eq!(guide.as_outcomes().len(), 2);
+        assert_eq!(skipped.errors().len(), 1);
+        assert_eq!(skipped.errors()[0].line, 2);
+
+        assert!(parse_guide(
+            Rules::rock_paper_scissors(),
+            "A Y\nB Q\n",
+            &mut Skipped::new(true)
+        )
+        .is_err());
+    }
+
+    /// A line of the strategy guide: the opponent's shape and our column
+    fn round_line() -> impl Strategy<Value = (usize, usize)> {
+        (0..3usize, 0..3usize)
+    }
+
+    proptest! {
+        #[test]
+        fn parse_round_never_panics(line in "\\PC*", is_part_one: bool) {
+            let _ = parse_round(&Rules::rock_paper_scissors(), &line, is_part_one);
+        }
+
+        #[test]
+        fn parse_round_round_trips((opponent, response) in round_line()) {
+            let rules = Rules::rock_paper_scissors();
+            let line = format!("{} {}", ["A", "B", "C"][opponent], ["X", "Y", "Z"][response]);
+            let round = parse_round(&rules, &line, true).unwrap();
+
+            prop_assert_eq!(Some(round), Round::new(&rules, opponent, response));
+            prop_assert!(parse_round(&rules, &line, false).is_ok());
+        }
+    }
+}
@@ -0,0 +1,341 @@
+//! Rules for rock-paper-scissors and its variants with more shapes, as data:
+//! the shapes, what each scores, and which shapes beat which
+
+use crate::Outcome;
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+
+/// Gestures of RPS-101, in the order of the official chart, where each beats
+/// the 50 gestures which follow it
+#[rustfmt::skip]
+const RPS_101: [&str; 101] = [
+    "Dynamite", "Tornado", "Quicksand", "Pit", "Chain", "Gun", "Law", "Whip", "Sword", "Rock",
+    "Death", "Wall", "Sun", "Camera", "Fire", "Chainsaw", "School", "Scissors", "Poison", "Cage",
+    "Axe", "Peace", "Computer", "Castle", "Snake", "Blood", "Porcupine", "Vulture", "Monkey",
+    "King", "Queen", "Prince", "Princess", "Police", "Woman", "Baby", "Man", "Home", "Train",
+    "Car", "Noise", "Bicycle", "Tree", "Turnip", "Duck", "Wolf", "Cat", "Bird", "Fish", "Spider",
+    "Cockroach", "Brain", "Community", "Cross", "Money", "Vampire", "Sponge", "Church", "Butter",
+    "Book", "Paper", "Cloud", "Airplane", "Moon", "Grass", "Film", "Toilet", "Air", "Planet",
+    "Guitar", "Bowl", "Cup", "Beer", "Rain", "Water", "TV", "Rainbow", "UFO", "Alien", "Prayer",
+    "Mountain", "Satan", "Dragon", "Diamond", "Platinum", "Gold", "Devil", "Fence", "Video Game",
+    "Math", "Robot", "Heart", "Electricity", "Lightning", "Medusa", "Power", "Laser", "Nuke",
+    "Sky", "Tank", "Helicopter",
+];
+
+/// Why a set of rules cannot be played
+#[derive(Debug, PartialEq)]
+pub enum InvalidRules {
+    /// Every outcome needs at least three shapes
+    TooFewShapes,
+    /// A rule names a shape which does not exist
+    UnknownShape(usize),
+    /// A shape is said to beat itself
+    BeatsItself(usize),
+    /// Neither shape beats the other, or each beats the other
+    Undecided(usize, usize),
+    /// A shape beats every other shape, or none of them, so some outcomes
+    /// can't be reached against it
+    OneSided(usize),
+}
+
+impl fmt::Display for InvalidRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidRules::TooFewShapes => write!(f, "there must be at least three shapes"),
+            InvalidRules::UnknownShape(i) => write!(f, "there is no shape {}", i),
+            InvalidRules::BeatsItself(i) => write!(f, "shape {} beats itself", i),
+            InvalidRules::Undecided(i, j) => {
+                write!(
+                    f,
+                    "exactly one of shapes {} and {} must beat the other",
+                    i, j
+                )
+            }
+            InvalidRules::OneSided(i) => {
+                write!(f, "shape {} must beat some shapes and lose to others", i)
+            }
+        }
+    }
+}
+
+impl core::error::Error for InvalidRules {}
+
+/// Column of the strategy guide. Each codes shapes with letters of its own,
+/// in the order of the rules.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Column {
+    /// Coded `A`, `B`, `C`...
+    Opponent,
+    /// Coded `X`, `Y`, `Z`, then wrapping around to `A`, `B`...
+    Response,
+}
+
+/// A game of rock-paper-scissors. Shapes are numbered from 0 in the order
+/// they were given, and every pair of different shapes has a winner.
+#[derive(Clone, Debug)]
+pub struct Rules {
+    names: Vec<String>,
+    scores: Vec<u32>,
+    /// `beats[i][j]` when shape `i` beats shape `j`
+    beats: Vec<Vec<bool>>,
+}
+
+impl Rules {
+    /// Rules for shapes given as `(name, score)`, where each `(i, j)` in
+    /// `beats` means shape `i` beats shape `j`
+    pub fn new(shapes: &[(&str, u32)], beats: &[(usize, usize)]) -> Result<Self, InvalidRules> {
+        let n = shapes.len();
+        if n < 3 {
+            return Err(InvalidRules::TooFewShapes);
+        }
+
+        let mut matrix = vec![vec![false; n]; n];
+        for &(i, j) in beats {
+            match (i < n, j < n) {
+                (false, _) => return Err(InvalidRules::UnknownShape(i)),
+                (_, false) => return Err(InvalidRules::UnknownShape(j)),
+                _ if i == j => return Err(InvalidRules::BeatsItself(i)),
+                _ => matrix[i][j] = true,
+            }
+        }
+
+        for (i, row) in matrix.iter().enumerate() {
+            if let Some(j) = (i + 1..n).find(|&j| row[j] == matrix[j][i]) {
+                return Err(InvalidRules::Undecided(i, j));
+            }
+
+            let wins = row.iter().filter(|&&beats| beats).count();
+            if wins == 0 || wins == n - 1 {
+                return Err(InvalidRules::OneSided(i));
+            }
+        }
+
+        Ok(Rules {
+            names: shapes.iter().map(|(name, _)| name.to_string()).collect(),
+            scores: shapes.iter().map(|&(_, score)| score).collect(),
+            beats: matrix,
+        })
+    }
+
+    /// Rules for an odd number of shapes arranged in a cycle, where each
+    /// beats the half of the other shapes which come just before it (wrapping
+    /// around). Shapes score 1, 2, 3... in order.
+    pub fn cyclic(names: &[&str]) -> Result<Self, InvalidRules> {
+        let n = names.len();
+        let shapes: Vec<(&str, u32)> = names.iter().zip(1..).map(|(&name, i)| (name, i)).collect();
+        let beats: Vec<(usize, usize)> = (0..n)
+            .flat_map(|i| (1..=n / 2).map(move |back| (i, (i + n - back) % n)))
+            .collect();
+
+        Self::new(&shapes, &beats)
+    }
+
+    /// The classic game, as played in the puzzle
+    pub fn rock_paper_scissors() -> Self {
+        Self::cyclic(&["Rock", "Paper", "Scissors"]).expect("a cycle of 3 shapes is valid")
+    }
+
+    /// Rock-paper-scissors-lizard-Spock, where each shape beats two others
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::cyclic(&["Rock", "Spock", "Paper", "Lizard", "Scissors"])
+            .expect("a cycle of 5 shapes is valid")
+    }
+
+    /// RPS-101, where each of 101 gestures beats 50 others
+    pub fn rps_101() -> Self {
+        // The chart lists what each gesture beats after it, rather than before
+        let mut names = RPS_101;
+        names.reverse();
+
+        Self::cyclic(&names).expect("a cycle of 101 shapes is valid")
+    }
+
+    /// Number of shapes which can be thrown
+    pub fn n_shapes(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Look up a shape by name
+    pub fn shape(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// Shape coded in a column of the strategy guide: a letter for any of the
+    /// first 26 shapes, or otherwise the shape's name
+    pub fn decode(&self, column: Column, code: &str) -> Option<usize> {
+        let first = match column {
+            Column::Opponent => b'A',
+            Column::Response => b'X',
+        };
+
+        match *code.as_bytes() {
+            [letter @ b'A'..=b'Z'] => {
+                let shape = usize::from((letter + 26 - first) % 26);
+                (shape < self.n_shapes()).then_some(shape)
+            }
+            _ => self.shape(code),
+        }
+    }
+
+    /// Name of a shape
+    pub fn name(&self, shape: usize) -> Option<&str> {
+        self.names.get(shape).map(String::as_str)
+    }
+
+    /// Score for throwing a shape
+    pub fn score(&self, shape: usize) -> Option<u32> {
+        self.scores.get(shape).copied()
+    }
+
+    /// Whether shape `i` beats shape `j`
+    pub fn beats(&self, i: usize, j: usize) -> Option<bool> {
+        self.beats.get(i)?.get(j).copied()
+    }
+
+    /// Outcome of throwing `response` against `opponent`
+    pub fn outcome(&self, response: usize, opponent: usize) -> Option<Outcome> {
+        let outcome = if self.beats(response, opponent)? {
+            Outcome::Win
+        } else if response == opponent {
+            Outcome::Draw
+        } else {
+            Outcome::Loss
+        };
+
+        Some(outcome)
+    }
+
+    /// First shape which meets the outcome against `opponent`. Valid rules
+    /// always have one, if they have the opponent's shape.
+    pub fn response(&self, opponent: usize, outcome: Outcome) -> Option<usize> {
+        (0..self.n_shapes()).find(|&shape| self.outcome(shape, opponent) == Some(outcome))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_rules() {
+        let rpsls = Rules::rock_paper_scissors_lizard_spock();
+        let shape = |name| rpsls.shape(name).unwrap();
+
+        // Each of the ten verbs of the game
+        for (winner, loser) in [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ] {
+            assert_eq!(
+                rpsls.outcome(shape(winner), shape(loser)),
+                Some(Outcome::Win)
+            );
+            assert_eq!(
+                rpsls.outcome(shape(loser), shape(winner)),
+                Some(Outcome::Loss)
+            );
+        }
+
+        let rps_101 = Rules::rps_101();
+        let dynamite = rps_101.shape("Dynamite").unwrap();
+        assert_eq!(rps_101.n_shapes(), 101);
+        assert_eq!(
+            rps_101.beats(dynamite, rps_101.shape("Tornado").unwrap()),
+            Some(true)
+        );
+        assert_eq!(
+            rps_101.beats(rps_101.shape("Helicopter").unwrap(), dynamite),
+            Some(true)
+        );
+        for shape in 0..101 {
+            let wins = (0..101)
+                .filter(|&other| rps_101.beats(shape, other) == Some(true))
+                .count();
+            assert_eq!(wins, 50);
+        }
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let shapes = [("Rock", 1), ("Paper", 2), ("Scissors", 3)];
+
+        assert!(Rules::new(&shapes, &[(1, 0), (2, 1), (0, 2)]).is_ok());
+        assert_eq!(
+            Rules::new(&shapes[..2], &[(1, 0)]).unwrap_err(),
+            InvalidRules::TooFewShapes
+        );
+        assert_eq!(
+            Rules::new(&shapes, &[(1, 0), (2, 1), (0, 3)]).unwrap_err(),
+            InvalidRules::UnknownShape(3)
+        );
+        assert_eq!(
+            Rules::new(&shapes, &[(1, 1)]).unwrap_err(),
+            InvalidRules::BeatsItself(1)
+        );
+        assert_eq!(
+            Rules::new(&shapes, &[(1, 0), (2, 1)]).unwrap_err(),
+            InvalidRules::Undecided(0, 2)
+        );
+        assert_eq!(
+            Rules::new(&shapes, &[(1, 0), (1, 2), (0, 2)]).unwrap_err(),
+            InvalidRules::OneSided(1)
+        );
+        assert_eq!(
+            Rules::cyclic(&["Rock", "Paper", "Scissors", "Well"]).unwrap_err(),
+            InvalidRules::Undecided(0, 2)
+        );
+    }
+
+    #[test]
+    fn test_response() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+
+        for opponent in 0..rules.n_shapes() {
+            for outcome in [Outcome::Win, Outcome::Draw, Outcome::Loss] {
+                let response = rules.response(opponent, outcome).unwrap();
+                assert_eq!(rules.outcome(response, opponent), Some(outcome));
+            }
+        }
+
+        assert_eq!(rules.response(5, Outcome::Win), None);
+        assert_eq!(rules.outcome(0, 5), None);
+        assert_eq!(rules.score(5), None);
+        assert_eq!(rules.name(5), None);
+    }
+
+    #[test]
+    fn test_decode() {
+        let rules = Rules::rock_paper_scissors();
+        assert_eq!(rules.decode(Column::Opponent, "C"), Some(2));
+        assert_eq!(rules.decode(Column::Response, "Z"), Some(2));
+        assert_eq!(rules.decode(Column::Opponent, "D"), None);
+        assert_eq!(rules.decode(Column::Response, "A"), None);
+
+        let rpsls = Rules::rock_paper_scissors_lizard_spock();
+        let decode = |column, code| rpsls.decode(column, code).and_then(|s| rpsls.name(s));
+        assert_eq!(decode(Column::Opponent, "B"), Some("Spock"));
+        assert_eq!(decode(Column::Opponent, "E"), Some("Scissors"));
+        assert_eq!(decode(Column::Response, "A"), Some("Lizard"));
+        assert_eq!(decode(Column::Response, "B"), Some("Scissors"));
+        assert_eq!(decode(Column::Opponent, "F"), None);
+        assert_eq!(decode(Column::Response, "C"), None);
+
+        let rps_101 = Rules::rps_101();
+        let decode = |code| rps_101.decode(Column::Opponent, code);
+        assert_eq!(decode("Video Game"), rps_101.shape("Video Game"));
+        assert_eq!(decode("Z"), Some(25));
+        assert_eq!(decode("Nothing"), None);
+    }
+}
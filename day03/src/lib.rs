@@ -0,0 +1,334 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use aoc_common::{each_line, Streaming};
+use aoc_common::{Error, ParseError, Skipped, Solution};
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::{io::BufRead, string::ToString};
+
+pub mod part1;
+pub mod part2;
+
+/// Types of item packed in each compartment of a rucksack, as bitmasks with
+/// bit `p` set for the item of priority `p`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rucksack {
+    pub compartments: [u64; 2],
+}
+
+impl Rucksack {
+    /// Read the items (letters) of a rucksack, split evenly between its
+    /// compartments
+    pub fn new(line: &str) -> Result<Self, ParseError> {
+        if !line.len().is_multiple_of(2) {
+            return Err(ParseError::line(line, "compartments must be the same size"));
+        }
+
+        let mut compartments = [0, 0];
+        for (i, c) in line.char_indices() {
+            let priority = part1::priority(c).ok_or_else(|| {
+                let item = &line[i..i + c.len_utf8()];
+                ParseError::at(line, item, "items must be letters")
+            })?;
+            compartments[2 * i / line.len()] |= 1 << priority;
+        }
+
+        Ok(Rucksack { compartments })
+    }
+
+    /// Every type of item in the rucksack
+    pub fn items(&self) -> u64 {
+        self.compartments[0] | self.compartments[1]
+    }
+}
+
+/// Check that a line describes a rucksack: an even number of items (letters),
+/// with one type of item packed in both compartments
+pub fn parse_rucksack(line: &str) -> Result<Rucksack, ParseError> {
+    let rucksack = Rucksack::new(line)?;
+
+    if part1::shared_item(&rucksack).is_none() {
+        return Err(ParseError::line(line, "compartments share no item"));
+    }
+
+    Ok(rucksack)
+}
+
+/// Collects rucksacks into groups of three elves, passing on each group
+/// which shares a badge and skipping those which do not, until a skipped line
+/// leaves the groups unknown
+struct Groups {
+    group: Vec<Rucksack>,
+    first_line: usize,
+    first_text: String,
+    misaligned: Option<ParseError>,
+}
+
+impl Groups {
+    fn new() -> Self {
+        Groups {
+            group: Vec::with_capacity(3),
+            first_line: 0,
+            first_text: String::new(),
+            misaligned: None,
+        }
+    }
+
+    /// Add the rucksack read from a line, passing on the group once complete
+    fn push<F>(
+        &mut self,
+        line: usize,
+        text: &str,
+        rucksack: Rucksack,
+        skipped: &mut Skipped,
+        f: &mut F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&[Rucksack; 3]),
+    {
+        if self.misaligned.is_some() {
+            return Ok(());
+        }
+
+        // Only the first line's text is kept, to report a group without a
+        // badge, in a buffer reused for every group
+        if self.group.is_empty() {
+            self.first_line = line;
+            self.first_text.clear();
+            self.first_text.push_str(text);
+        }
+        self.group.push(rucksack);
+
+        if let Ok(group) = <[Rucksack; 3]>::try_from(self.group.as_slice()) {
+            self.group.clear();
+            if part2::badge(&group).is_some() {
+                f(&group);
+            } else {
+                let e = ParseError::line(&self.first_text, "group shares no badge");
+                skipped.skip(e.with_line(self.first_line))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Note a skipped line, which may or may not have been an elf's, so that
+    /// the groups which follow it are unknown
+    fn skip_line(&mut self, line: usize, text: &str) {
+        if self.misaligned.is_none() {
+            let e = ParseError::line(text, "groups are unknown after this line was skipped");
+            self.misaligned = Some(e.with_line(line));
+        }
+    }
+
+    /// Skip a final, incomplete group, returning the line after which groups
+    /// became unknown if a skipped line broke them
+    fn finish(self, skipped: &mut Skipped) -> Result<Option<ParseError>, Error> {
+        if self.misaligned.is_none() && !self.group.is_empty() {
+            let e = ParseError::line(&self.first_text, "group has fewer than three elves");
+            skipped.skip(e.with_line(self.first_line))?;
+        }
+
+        Ok(self.misaligned)
+    }
+}
+
+/// Rucksacks, one per line, and the groups of three elves carrying them
+pub struct Rucksacks {
+    /// Every rucksack whose compartments share an item
+    pub rucksacks: Vec<Rucksack>,
+    /// Every complete group of three which shares a badge
+    pub groups: Vec<[Rucksack; 3]>,
+    /// The skipped line after which groups are unknown, if any
+    pub misaligned: Option<ParseError>,
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Rucksacks;
+
+    fn parse<'a>(input: &'a str, skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        let mut rucksacks = Vec::new();
+        let mut groups = Vec::new();
+        let mut keep = |group: &[Rucksack; 3]| groups.push(*group);
+
+        let mut grouping = Groups::new();
+        for (i, line) in input.lines().enumerate() {
+            match parse_rucksack(line) {
+                Ok(rucksack) => {
+                    rucksacks.push(rucksack);
+                    grouping.push(i + 1, line, rucksack, skipped, &mut keep)?;
+                }
+                Err(e) => {
+                    skipped.skip(e.with_line(i + 1))?;
+                    grouping.skip_line(i + 1, line);
+                }
+            }
+        }
+        let misaligned = grouping.finish(skipped)?;
+
+        Ok(Rucksacks {
+            rucksacks,
+            groups,
+            misaligned,
+        })
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(part1::total_priority(&input.rucksacks))
+    }
+
+    /// Fails if a skipped line leaves the groups unknown
+    fn part2(input: &Self::Input<'_>) -> Result<impl Display, Error> {
+        match &input.misaligned {
+            Some(e) => Err(e.clone().into()),
+            None => Ok(part2::total_priority(&input.groups)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Streaming for Day3 {
+    /// Hold one group of rucksacks at a time
+    fn stream<R: BufRead>(reader: R, skipped: &mut Skipped) -> Result<[String; 2], Error> {
+        let (mut part1_total, mut part2_total) = (0, 0);
+        let mut add = |group: &[Rucksack; 3]| part2_total += part2::total_priority(&[*group]);
+
+        let mut grouping = Groups::new();
+        each_line(reader, |i, line| {
+            match parse_rucksack(line) {
+                Ok(rucksack) => {
+                    part1_total += part1::total_priority(&[rucksack]);
+                    grouping.push(i, line, rucksack, skipped, &mut add)?;
+                }
+                Err(e) => {
+                    skipped.skip(e.with_line(i))?;
+                    grouping.skip_line(i, line);
+                }
+            }
+
+            Ok(())
+        })?;
+        if let Some(e) = grouping.finish(skipped)? {
+            return Err(e.into());
+        }
+
+        Ok([part1_total, part2_total].map(|total| total.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    /// Answers from parsing the whole input and from streaming it, which must
+    /// agree, along with the lines skipped
+    fn solve(input: &str) -> (Result<[String; 2], Error>, Vec<usize>) {
+        let mut skipped = Skipped::new(false);
+        let parsed = Day3::parse(input, &mut skipped).unwrap();
+        let answers = Day3::part2(&parsed).map(|part2| {
+            let part1 = Day3::part1(&parsed).unwrap();
+            [part1.to_string(), part2.to_string()]
+        });
+
+        let mut streamed = Skipped::new(false);
+        let stream = Day3::stream(input.as_bytes(), &mut streamed);
+        assert_eq!(answers.as_ref().ok(), stream.as_ref().ok());
+        assert_eq!(skipped.errors(), streamed.errors());
+
+        (answers, skipped.errors().iter().map(|e| e.line).collect())
+    }
+
+    #[test]
+    fn test_skipped_line() {
+        // Skipping the second line leaves it unknown which elves are grouped
+        let input = EXAMPLE.replacen('\n', "\nabcd\n", 1);
+        let mut skipped = Skipped::new(false);
+        let parsed = Day3::parse(&input, &mut skipped).unwrap();
+
+        assert_eq!(Day3::part1(&parsed).unwrap().to_string(), "157");
+        match Day3::part2(&parsed).map(|answer| answer.to_string()) {
+            Err(Error::Parse(e)) => assert_eq!((e.line, e.text.as_str()), (2, "abcd")),
+            _ => panic!("expected part 2 to fail"),
+        }
+        assert_eq!(solve(&input).1, [2]);
+    }
+
+    #[test]
+    fn test_group_without_badge() {
+        // Every rucksack still counts towards part 1
+        let input = EXAMPLE.replacen("PmmdzqPrVvPwwTWBwg", "aa", 1);
+        let (answers, skipped) = solve(&input);
+
+        assert_eq!(answers.unwrap(), ["116", "52"]);
+        assert_eq!(skipped, [1]);
+    }
+
+    #[test]
+    fn test_incomplete_group() {
+        let input = format!("{}aa\nbb\n", EXAMPLE);
+        let (answers, skipped) = solve(&input);
+
+        assert_eq!(answers.unwrap(), ["160", "70"]);
+        assert_eq!(skipped, [7]);
+
+        assert!(Day3::parse(&input, &mut Skipped::new(true)).is_err());
+    }
+
+    #[test]
+    fn test_parse_rucksack() {
+        let rucksack = parse_rucksack("abca").unwrap();
+        assert_eq!(rucksack.compartments, [0b110, 0b1010]);
+        assert_eq!(rucksack.items(), 0b1110);
+
+        let err = parse_rucksack("ab1a").err().unwrap();
+        assert_eq!(err.column, 3);
+        assert_eq!(err.text, "1");
+
+        assert!(parse_rucksack("abcab").is_err());
+        assert!(parse_rucksack("abcd").is_err());
+    }
+
+    /// A rucksack whose compartments share exactly the returned item
+    fn rucksack() -> impl Strategy<Value = (String, char)> {
+        (1..20usize, "[a-zA-Z]")
+            .prop_flat_map(|(n, shared)| {
+                let first = prop::collection::vec(prop::char::range('a', 'z'), n - 1);
+                let second = prop::collection::vec(prop::char::range('A', 'Z'), n - 1);
+                (first, second, Just(shared.chars().next().unwrap()))
+            })
+            .prop_map(|(mut first, mut second, shared)| {
+                // Compartments are otherwise disjoint, one lower and one upper case
+                first.retain(|&c| c != shared);
+                second.retain(|&c| c != shared);
+                let n = first.len().min(second.len());
+                first.truncate(n);
+                second.truncate(n);
+                first.push(shared);
+                second.insert(0, shared);
+
+                (first.into_iter().chain(second).collect(), shared)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn parse_rucksack_never_panics(line in "\\PC*") {
+            let _ = parse_rucksack(&line);
+        }
+
+        #[test]
+        fn parse_rucksack_round_trips((line, shared) in rucksack()) {
+            let rucksack = parse_rucksack(&line).unwrap();
+            prop_assert_eq!(part1::shared_item(&rucksack), part1::priority(shared));
+        }
+    }
+}
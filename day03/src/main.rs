@@ -0,0 +1,23 @@
+use aoc_common::{get_args, load_input, Args, Error, Skipped, Solution};
+use day3::Day3;
+
+fn main() {
+    if let Err(e) = get_args().and_then(run) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<(), Error> {
+    let input = load_input(&args.fin)?;
+    let mut skipped = Skipped::new(args.strict);
+
+    println!(
+        "Total priority: {}",
+        Day3::solve(&input, args.part2, &mut skipped)?
+    );
+
+    skipped.report();
+
+    Ok(())
+}
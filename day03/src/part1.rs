@@ -0,0 +1,59 @@
+use crate::Rucksack;
+use tracing::trace;
+
+/// Compute "priority" of an item (ascii value - offset), or `None` unless it
+/// is a letter
+pub fn priority(c: char) -> Option<u32> {
+    // problem formulated to have different offsets for lower/upper
+    // a..z = 1..26; A..Z = 27..52
+    match c {
+        'A'..='Z' => Some((c as u32) - 38),
+        'a'..='z' => Some((c as u32) - 96),
+        _ => None,
+    }
+}
+
+/// Priority of the item type packed in both compartments of a rucksack.
+/// Assumes only one is shared, and reports the lowest priority found.
+pub fn shared_item(rucksack: &Rucksack) -> Option<u32> {
+    let shared = rucksack.compartments[0] & rucksack.compartments[1];
+
+    (shared != 0).then(|| shared.trailing_zeros())
+}
+
+/// Sum the priorities of the item shared by both compartments of each rucksack
+pub fn total_priority(rucksacks: &[Rucksack]) -> u64 {
+    let mut total_priority = 0;
+    for rucksack in rucksacks {
+        // Rucksacks are checked for a shared item when parsing
+        let shared = shared_item(rucksack).unwrap();
+        trace!(priority = shared, "shared by both compartments");
+        total_priority += u64::from(shared);
+    }
+
+    total_priority
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority() {
+        assert_eq!(priority('a'), Some(1));
+        assert_eq!(priority('z'), Some(26));
+        assert_eq!(priority('A'), Some(27));
+        assert_eq!(priority('Z'), Some(52));
+        assert_eq!(priority('1'), None);
+        assert_eq!(priority('é'), None);
+    }
+
+    #[test]
+    fn test_shared_item() {
+        let rucksack = Rucksack::new("ABABDEAB").unwrap();
+        assert_eq!(shared_item(&rucksack), priority('A'));
+
+        let rucksack = Rucksack::new("abcd").unwrap();
+        assert_eq!(shared_item(&rucksack), None);
+    }
+}
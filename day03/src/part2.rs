@@ -0,0 +1,44 @@
+use crate::Rucksack;
+use tracing::trace;
+
+/// Priority of the item type found in every rucksack of a group, reporting
+/// the lowest if there are several
+pub fn badge(group: &[Rucksack]) -> Option<u32> {
+    let common = group
+        .iter()
+        .map(Rucksack::items)
+        .reduce(|x, y| x & y)
+        .unwrap_or(0);
+
+    (common != 0).then(|| common.trailing_zeros())
+}
+
+/// Sum the priorities of the badge item shared by each group of three elves
+pub fn total_priority(groups: &[[Rucksack; 3]]) -> u64 {
+    let mut total_priority = 0;
+
+    for group in groups {
+        // Groups are checked for a common letter when parsing
+        let badge = badge(group).unwrap();
+        trace!(priority = badge, "group");
+        total_priority += u64::from(badge);
+    }
+
+    total_priority
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part1::priority;
+
+    #[test]
+    fn test_badge() {
+        let group = ["sbsb", "scsc", "xsxs"].map(|line| Rucksack::new(line).unwrap());
+        assert_eq!(badge(&group), priority('s'));
+
+        let group = ["abab", "cdcd"].map(|line| Rucksack::new(line).unwrap());
+        assert_eq!(badge(&group), None);
+        assert_eq!(badge(&[]), None);
+    }
+}
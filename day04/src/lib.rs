@@ -1,20 +1,54 @@
-use std::ops::Range;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-fn parse_range(s: &str) -> Range<i32> {
-    let r: Vec<i32> = s.split("-").filter_map(|x| x.parse::<i32>().ok()).collect();
+extern crate alloc;
 
-    Range {
-        start: r[0],
-        end: r[1] + 1,
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use aoc_common::{each_line, Streaming};
+use aoc_common::{parse_lines, Error, ParseError, Skipped, Solution};
+use core::{fmt::Display, ops::Range};
+#[cfg(feature = "std")]
+use std::{io::BufRead, string::String};
+
+fn parse_section(s: &str, id: &str) -> Result<i32, ParseError> {
+    id.parse::<i32>()
+        .map_err(|_| ParseError::at(s, id, "expected a section ID"))
+}
+
+fn parse_range(s: &str) -> Result<Range<i32>, ParseError> {
+    let (start, last) = s
+        .split_once('-')
+        .ok_or_else(|| ParseError::line(s, "expected a range like 2-4"))?;
+    let start = parse_section(s, start)?;
+    let end = parse_section(s, last)?;
+
+    if start > end {
+        return Err(ParseError::line(s, "range ends before it starts"));
     }
+
+    // Ranges exclude their end, so the last section must have one after it
+    let end = end
+        .checked_add(1)
+        .ok_or_else(|| ParseError::at(s, last, "section ID is too large"))?;
+
+    Ok(Range { start, end })
 }
 
-pub fn parse_ranges(line: &str) -> Vec<Range<i32>> {
-    line.split(",").map(|x| parse_range(x)).collect()
+pub fn parse_ranges(line: &str) -> Result<Vec<Range<i32>>, ParseError> {
+    let ranges = line
+        .split(',')
+        .map(|s| parse_range(s).map_err(|e| e.within(line, s)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if ranges.len() != 2 {
+        return Err(ParseError::line(line, "expected a pair of ranges"));
+    }
+
+    Ok(ranges)
 }
 
 fn is_contained(r1: &Range<i32>, r2: &Range<i32>) -> bool {
-    r1.contains(&r2.start) && r1.contains(&(&r2.end - 1))
+    r1.contains(&r2.start) && r1.contains(&(r2.end - 1))
 }
 
 pub fn either_contains(r1: &Range<i32>, r2: &Range<i32>) -> bool {
@@ -23,20 +57,63 @@ pub fn either_contains(r1: &Range<i32>, r2: &Range<i32>) -> bool {
 
 pub fn overlaps(r1: &Range<i32>, r2: &Range<i32>) -> bool {
     r1.contains(&r2.start)
-        || r1.contains(&(&r2.end - 1))
+        || r1.contains(&(r2.end - 1))
         || r2.contains(&r1.start)
-        || r2.contains(&(&r1.end - 1))
+        || r2.contains(&(r1.end - 1))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    /// Pair of section assignments for each line
+    type Input<'a> = Vec<Vec<Range<i32>>>;
+
+    fn parse<'a>(input: &'a str, skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        parse_lines(input, skipped, parse_ranges)
+    }
+
+    fn part1(pairs: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(pairs
+            .iter()
+            .filter(|x| either_contains(&x[0], &x[1]))
+            .count())
+    }
+
+    fn part2(pairs: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(pairs.iter().filter(|x| overlaps(&x[0], &x[1])).count())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Streaming for Day4 {
+    fn stream<R: BufRead>(reader: R, skipped: &mut Skipped) -> Result<[String; 2], Error> {
+        let mut counts = [0, 0];
+        each_line(reader, |i, line| {
+            match parse_ranges(line) {
+                Ok(pair) => {
+                    counts[0] += either_contains(&pair[0], &pair[1]) as usize;
+                    counts[1] += overlaps(&pair[0], &pair[1]) as usize;
+                }
+                Err(e) => skipped.skip(e.with_line(i))?,
+            }
+
+            Ok(())
+        })?;
+
+        Ok(counts.map(|count| count.to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_parse_range() {
         let start = 2;
         let end = 6;
-        let r = parse_range(&format!("{}-{}", start, end));
+        let r = parse_range(&format!("{}-{}", start, end)).unwrap();
 
         assert_eq!(r.start, start);
         assert_eq!(r.end, end + 1);
@@ -44,12 +121,25 @@ mod tests {
 
     #[test]
     fn test_parse_ranges() {
-        let ranges = parse_ranges("2-6,3-4");
+        let ranges = parse_ranges("2-6,3-4").unwrap();
 
         assert_eq!(ranges[0].start, 2);
         assert_eq!(ranges[0].end, 7);
         assert_eq!(ranges[1].start, 3);
         assert_eq!(ranges[1].end, 5);
+
+        let err = parse_ranges("2-6,3-x").unwrap_err();
+        assert_eq!(err.column, 7);
+        assert_eq!(err.text, "x");
+
+        assert!(parse_ranges("2-6").is_err());
+        assert!(parse_ranges("2-6,3-4,5-6").is_err());
+        assert!(parse_ranges("6-2,3-4").is_err());
+
+        let err = parse_ranges("1-2147483647,3-4").unwrap_err();
+        assert_eq!(err.column, 3);
+        assert_eq!(err.text, "2147483647");
+        assert!(parse_ranges("1-2147483646,3-4").is_ok());
     }
 
     #[test]
@@ -58,4 +148,34 @@ mod tests {
         let r2 = Range { start: 6, end: 7 };
         assert!(is_contained(&r1, &r2));
     }
+
+    /// A section range, as its first and last section IDs
+    fn range() -> impl Strategy<Value = (i32, i32)> {
+        (0..1000i32, 0..100i32).prop_map(|(start, len)| (start, start + len))
+    }
+
+    proptest! {
+        #[test]
+        fn parse_ranges_never_panics(line in "\\PC*") {
+            let _ = parse_ranges(&line);
+        }
+
+        #[test]
+        fn parse_ranges_round_trips(first in range(), second in range()) {
+            let line = format!("{}-{},{}-{}", first.0, first.1, second.0, second.1);
+            let ranges = parse_ranges(&line).unwrap();
+
+            prop_assert_eq!(ranges, vec![first.0..first.1 + 1, second.0..second.1 + 1]);
+        }
+
+        #[test]
+        fn parse_ranges_never_panics_at_bounds(
+            ids in prop::array::uniform4(prop_oneof![Just(i32::MIN), Just(i32::MAX), any::<i32>()])
+        ) {
+            let line = format!("{}-{},{}-{}", ids[0], ids[1], ids[2], ids[3]);
+            if let Ok(ranges) = parse_ranges(&line) {
+                prop_assert!(ranges.iter().all(|r| r.start < r.end));
+            }
+        }
+    }
 }
@@ -1,15 +1,5 @@
-use clap::Parser;
-use std::{
-    error::Error,
-    fs::File,
-    io::{self, BufRead, BufReader},
-};
-
-#[derive(Parser, Debug)]
-pub struct Args {
-    #[arg(help = "Input file", id = "FILE", default_value = "-")]
-    fin: String,
-}
+use aoc_common::{get_args, load_input, Args, Error, Skipped, Solution};
+use day4::Day4;
 
 fn main() {
     if let Err(e) = get_args().and_then(run) {
@@ -18,34 +8,18 @@ fn main() {
     }
 }
 
-fn get_args() -> Result<Args, Box<dyn Error>> {
-    let args = Args::parse();
-
-    Ok(args)
-}
-
-fn run(args: Args) -> Result<(), Box<dyn Error>> {
-    let fin = open(&args.fin)?;
+fn run(args: Args) -> Result<(), Error> {
+    let input = load_input(&args.fin)?;
+    let mut skipped = Skipped::new(args.strict);
+    let n_overlap = Day4::solve(&input, args.part2, &mut skipped)?;
 
-    let n_overlap = fin
-        .lines()
-        .filter_map(|x| x.ok())
-        .map(|line| day4::parse_ranges(&(line)))
-        // .filter(|x| day4::either_contains(&x[0], &x[1]))
-        .filter(|x| day4::overlaps(&x[0], &x[1]))
-        .count();
+    if args.part2 {
+        println!("{} pairs partially overlap", n_overlap);
+    } else {
+        println!("{} pairs fully overlap", n_overlap);
+    }
 
-    // println!("{} pairs fully overlap", n_overlap);
-    println!("{} pairs partially overlap", n_overlap);
+    skipped.report();
 
     Ok(())
 }
-
-fn open(filename: &str) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(
-            File::open(filename).map_err(|e| format!("{}: {}", filename, e))?,
-        ))),
-    }
-}
@@ -1,61 +1,308 @@
-pub fn parse_crates(line: &str) -> Vec<Option<char>> {
-    line.chars()
-        .collect::<Vec<char>>()
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{collections::VecDeque, format, string::String, vec, vec::Vec};
+use aoc_common::{Error, ParseError, Skipped, Solution};
+use core::fmt::Display;
+use tracing::{debug, trace};
+
+/// Parse a row of the crate diagram, where each stack takes up four columns
+/// holding either a crate like `[A] ` or blanks
+pub fn parse_crates(line: &str) -> Result<Vec<Option<char>>, ParseError> {
+    let line = line.trim_end_matches(['\n', '\r']);
+
+    line.char_indices()
+        .collect::<Vec<(usize, char)>>()
         .chunks(4)
         .map(|chunk| {
-            if chunk[0] == '[' {
-                Some(chunk[1])
-            } else {
-                None
+            let start = chunk[0].0;
+            let (end, last) = chunk[chunk.len() - 1];
+            let cell = &line[start..end + last.len_utf8()];
+
+            match cell.trim_end().as_bytes() {
+                [] => Ok(None),
+                [b'[', _, b']'] => Ok(Some(chunk[1].1)),
+                _ => Err(ParseError::at(line, cell, "expected a crate like [A]")),
             }
         })
         .collect()
 }
 
+#[derive(Debug)]
 pub struct Move {
     pub n: usize,
     pub src: usize,
     pub dst: usize,
 }
 
-pub fn parse_move(line: &str) -> Move {
-    let data: Vec<usize> = line
-        .split_whitespace()
-        .enumerate()
-        .filter(|(i, _)| i % 2 == 1)
-        .filter_map(|(_, x)| x.parse::<usize>().ok())
-        .collect();
+/// Parse a line like `move 1 from 2 to 3`, converting stack numbers to indices
+pub fn parse_move(line: &str) -> Result<Move, ParseError> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    if words.len() != 6 {
+        return Err(ParseError::line(
+            line,
+            "expected a move like \"move 1 from 2 to 3\"",
+        ));
+    }
+
+    for (i, keyword) in [(0, "move"), (2, "from"), (4, "to")] {
+        if words[i] != keyword {
+            return Err(ParseError::at(
+                line,
+                words[i],
+                format!("expected {:?}", keyword),
+            ));
+        }
+    }
+
+    let number = |word: &str| {
+        word.parse::<usize>()
+            .map_err(|_| ParseError::at(line, word, "expected a number"))
+    };
+    let stack = |word: &str| match number(word)? {
+        0 => Err(ParseError::at(line, word, "stacks are numbered from 1")),
+        i => Ok(i - 1),
+    };
+
+    Ok(Move {
+        n: number(words[1])?,
+        src: stack(words[3])?,
+        dst: stack(words[5])?,
+    })
+}
+
+/// Starting stacks of crates (front of each stack is the top crate), and the
+/// moves to apply to them
+pub struct Procedure {
+    pub stacks: Vec<VecDeque<char>>,
+    pub moves: Vec<Move>,
+}
+
+/// Parse a move, checking it against the current height of each stack
+fn parse_legal_move(line: &str, heights: &[usize]) -> Result<Move, ParseError> {
+    let mv = parse_move(line)?;
+
+    if mv.src >= heights.len() || mv.dst >= heights.len() {
+        let reason = format!("there are only {} stacks", heights.len());
+        return Err(ParseError::line(line, reason));
+    }
+    if mv.n > heights[mv.src] {
+        let reason = format!("stack {} only holds {} crates", mv.src + 1, heights[mv.src]);
+        return Err(ParseError::line(line, reason));
+    }
+
+    Ok(mv)
+}
+
+/// Parse the crate diagram and the list of moves which follows it.
+///
+/// Malformed rows of the diagram are always errors, while malformed or
+/// impossible moves are skipped unless `skipped` is strict.
+pub fn parse_procedure(input: &str, skipped: &mut Skipped) -> Result<Procedure, Error> {
+    let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+    // Load "crate contents" (characters) by row
+    let mut crate_rows: VecDeque<Vec<Option<char>>> = VecDeque::new();
+    loop {
+        let (n, line) = lines.next().ok_or_else(|| {
+            let last = input.lines().count();
+            ParseError::line("", "missing crate diagram").with_line(last)
+        })?;
+
+        if !line.trim_start().starts_with('[') {
+            // skip blank line following numbers
+            lines.next();
+            break;
+        }
 
-    Move {
-        n: data[0],
-        src: data[1] - 1,
-        dst: data[2] - 1,
+        let row = parse_crates(line).map_err(|e| e.with_line(n))?;
+        crate_rows.push_front(row);
+    }
+
+    // Transpose rows of crates into vertical stacks, discarding non-existent crates
+    let n_stacks = crate_rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut stacks: Vec<VecDeque<char>> = vec![VecDeque::new(); n_stacks];
+    for row in crate_rows {
+        for (i, copt) in row.iter().enumerate() {
+            if let Some(c) = copt {
+                stacks[i].push_front(*c);
+            }
+        }
+    }
+
+    // Check each move against the height of the stacks it would leave behind
+    let mut heights: Vec<usize> = stacks.iter().map(|stack| stack.len()).collect();
+    let mut moves = Vec::new();
+    for (n, line) in lines {
+        match parse_legal_move(line, &heights) {
+            Ok(mv) => {
+                heights[mv.src] -= mv.n;
+                heights[mv.dst] += mv.n;
+                moves.push(mv);
+            }
+            Err(e) => skipped.skip(e.with_line(n))?,
+        }
+    }
+
+    debug!(
+        stacks = stacks.len(),
+        moves = moves.len(),
+        "parsed procedure"
+    );
+    Ok(Procedure { stacks, moves })
+}
+
+/// Rearrange crates according to the procedure, reporting the top crate of each stack.
+///
+/// The CrateMover 9000 (part 1) moves crates one at a time, while the
+/// CrateMover 9001 (part 2) moves them as a stack.
+pub fn rearrange(procedure: &Procedure, as_stack: bool) -> String {
+    let mut stacks = procedure.stacks.clone();
+
+    for mv in &procedure.moves {
+        trace!(n = mv.n, from = mv.src + 1, to = mv.dst + 1, "move");
+        if as_stack {
+            // move crates as a stack, taking them off the source first in
+            // case it is also the destination
+            let moved: Vec<char> = stacks[mv.src].drain(..mv.n).collect();
+            for c in moved.into_iter().rev() {
+                stacks[mv.dst].push_front(c);
+            }
+        } else {
+            // move crates one at a time
+            for _ in 0..mv.n {
+                let c = stacks[mv.src].pop_front().unwrap();
+                stacks[mv.dst].push_front(c);
+            }
+        }
+    }
+
+    // Report first/top crate/character in each stack
+    stacks
+        .into_iter()
+        .filter_map(|x| x.front().copied())
+        .collect()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = Procedure;
+
+    fn parse<'a>(input: &'a str, skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        parse_procedure(input, skipped)
+    }
+
+    fn part1(procedure: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(rearrange(procedure, false))
+    }
+
+    fn part2(procedure: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(rearrange(procedure, true))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_parse_crates() {
         let line = "[A] [B]     [D]\n";
-        let crates = parse_crates(&line);
+        let crates = parse_crates(line).unwrap();
 
         assert_eq!(crates.len(), 4);
         assert_eq!(crates[0], Some('A'));
         assert_eq!(crates[1], Some('B'));
         assert_eq!(crates[2], None);
         assert_eq!(crates[3], Some('D'));
+
+        let err = parse_crates("[A] (B)").unwrap_err();
+        assert_eq!(err.column, 5);
+        assert_eq!(err.text, "(B)");
     }
 
     #[test]
     fn test_parse_move() {
         let line = "move 1 from 3 to 5\n";
-        let mv = parse_move(line);
+        let mv = parse_move(line).unwrap();
 
         assert_eq!(mv.n, 1);
         assert_eq!(mv.src, 2);
         assert_eq!(mv.dst, 4);
+
+        let err = parse_move("move 1 from x to 5").unwrap_err();
+        assert_eq!(err.column, 13);
+        assert_eq!(err.text, "x");
+
+        assert!(parse_move("move 1 from 0 to 5").is_err());
+        assert!(parse_move("move 1 from 3").is_err());
+    }
+
+    #[test]
+    fn test_parse_procedure() {
+        let diagram = "    [D]\n[N] [C]\n 1   2\n\n";
+        let strict = &mut Skipped::new(true);
+        assert!(parse_procedure(&format!("{}move 1 from 2 to 1\n", diagram), strict).is_ok());
+
+        let input = format!("{}move 1 from 2 to 1\nmove 3 from 2 to 1\n", diagram);
+        match parse_procedure(&input, strict) {
+            Err(Error::Parse(e)) => assert_eq!(e.line, 6),
+            _ => panic!("expected a parse error"),
+        }
+        assert!(parse_procedure(&format!("{}move 1 from 2 to 3\n", diagram), strict).is_err());
+
+        let mut skipped = Skipped::new(false);
+        let procedure = parse_procedure(&input, &mut skipped).unwrap();
+        assert_eq!(procedure.moves.len(), 1);
+        assert_eq!(skipped.errors()[0].line, 6);
+    }
+
+    #[test]
+    fn test_move_onto_same_stack() {
+        let input = "[D]    \n[N] [C]\n 1   2\n\nmove 1 from 1 to 1\nmove 2 from 1 to 2\n";
+        let procedure = parse_procedure(input, &mut Skipped::new(true)).unwrap();
+
+        assert_eq!(rearrange(&procedure, false), "N");
+        assert_eq!(rearrange(&procedure, true), "D");
+    }
+
+    /// A row of the crate diagram, with a crate or a gap in each stack
+    fn crate_row() -> impl Strategy<Value = Vec<Option<char>>> {
+        prop::collection::vec(prop::option::of(prop::char::range('A', 'Z')), 1..10)
+    }
+
+    proptest! {
+        #[test]
+        fn parse_crates_never_panics(line in "\\PC*") {
+            let _ = parse_crates(&line);
+        }
+
+        #[test]
+        fn parse_crates_round_trips(row in crate_row()) {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|c| match c {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".to_string(),
+                })
+                .collect();
+
+            prop_assert_eq!(parse_crates(&cells.join(" ")).unwrap(), row);
+        }
+
+        #[test]
+        fn parse_move_never_panics(line in "\\PC*") {
+            let _ = parse_move(&line);
+        }
+
+        #[test]
+        fn parse_move_round_trips(n in 0..100usize, src in 1..10usize, dst in 1..10usize) {
+            let mv = parse_move(&format!("move {} from {} to {}", n, src, dst)).unwrap();
+
+            prop_assert_eq!((mv.n, mv.src, mv.dst), (n, src - 1, dst - 1));
+        }
     }
 }
@@ -1,16 +1,217 @@
-use std::collections::HashSet;
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use aoc_common::Streaming;
+use aoc_common::{Error, ParseError, Skipped, Solution};
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::{
+    io::{self, BufRead},
+    string::String,
+};
+use tracing::debug;
+
+/// Position just after the first `n` distinct characters in a row (counting
+/// characters, not bytes), or `n` if there are none
 pub fn find_marker(buffer: &str, n: usize) -> usize {
+    // Any zero characters are distinct, so the marker is found before the first
+    if n == 0 {
+        return 0;
+    }
+
     let pos = buffer
         .chars()
         .collect::<Vec<_>>()
         .windows(n)
-        .position(|x| x.into_iter().collect::<HashSet<_>>().len() == n)
+        .position(|x| x.iter().collect::<BTreeSet<_>>().len() == n)
         .unwrap_or(0);
+    debug!(n, end = pos + n, "marker");
 
     pos + n
 }
 
+/// Scan a buffer one character at a time for a marker of `n` distinct
+/// characters, remembering only the last `n` characters
+pub struct MarkerScanner {
+    n: usize,
+    recent: VecDeque<char>,
+    counts: BTreeMap<char, usize>,
+    distinct: usize,
+    seen: usize,
+    marker: Option<usize>,
+}
+
+impl MarkerScanner {
+    pub fn new(n: usize) -> Self {
+        MarkerScanner {
+            n,
+            recent: VecDeque::with_capacity(n + 1),
+            counts: BTreeMap::new(),
+            distinct: 0,
+            seen: 0,
+            marker: (n == 0).then_some(0),
+        }
+    }
+
+    /// Add the next character, reporting the end of the marker once found
+    pub fn push(&mut self, c: char) -> Option<usize> {
+        if self.marker.is_some() {
+            return self.marker;
+        }
+
+        self.seen += 1;
+        self.recent.push_back(c);
+        let count = self.counts.entry(c).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            self.distinct += 1;
+        }
+
+        if self.recent.len() > self.n {
+            let old = self.recent.pop_front().unwrap();
+            let count = self.counts.get_mut(&old).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&old);
+                self.distinct -= 1;
+            }
+        }
+
+        if self.distinct == self.n {
+            self.marker = Some(self.seen);
+        }
+
+        self.marker
+    }
+}
+
+/// Decodes UTF-8 a byte at a time, for characters split between reads
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct Utf8Decoder {
+    bytes: [u8; 4],
+    len: usize,
+}
+
+#[cfg(feature = "std")]
+impl Utf8Decoder {
+    /// Add the next byte, returning the character it completes, if any
+    fn push(&mut self, b: u8) -> Result<Option<char>, Error> {
+        self.bytes[self.len] = b;
+        self.len += 1;
+
+        match core::str::from_utf8(&self.bytes[..self.len]) {
+            Ok(s) => {
+                self.len = 0;
+                Ok(s.chars().next())
+            }
+            Err(e) if e.error_len().is_none() => Ok(None),
+            Err(_) => Err(invalid_utf8()),
+        }
+    }
+}
+
+/// Error for a stream which is not UTF-8, as `read_to_string` reports it
+#[cfg(feature = "std")]
+fn invalid_utf8() -> Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+    .into()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    /// The datastream buffer
+    type Input<'a> = &'a str;
+
+    fn parse<'a>(input: &'a str, _skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        input
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::line("", "empty input").with_line(1).into())
+    }
+
+    /// Find the start-of-packet marker (4 distinct characters)
+    fn part1(buffer: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(find_marker(buffer, 4))
+    }
+
+    /// Find the start-of-message marker (14 distinct characters)
+    fn part2(buffer: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(find_marker(buffer, 14))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Streaming for Day6 {
+    /// Scan the first line of the input for both markers, stopping once found.
+    /// Like `str::lines`, a `\r` is left out of the line when it ends it.
+    fn stream<R: BufRead>(mut reader: R, _skipped: &mut Skipped) -> Result<[String; 2], Error> {
+        let mut scanners = [MarkerScanner::new(4), MarkerScanner::new(14)];
+        let mut decoder = Utf8Decoder::default();
+        let mut carriage_return = false;
+        let mut empty = true;
+
+        let mut push = |c: char| {
+            let mut all_found = true;
+            for scanner in &mut scanners {
+                all_found &= scanner.push(c).is_some();
+            }
+            all_found
+        };
+
+        'scan: loop {
+            let chunk = reader.fill_buf()?;
+            if chunk.is_empty() {
+                if decoder.len > 0 {
+                    return Err(invalid_utf8());
+                }
+                if carriage_return {
+                    push('\r');
+                }
+                break;
+            }
+            empty = false;
+
+            let mut used = 0;
+            for &b in chunk {
+                used += 1;
+                let Some(c) = decoder.push(b)? else {
+                    continue;
+                };
+                if c == '\n' {
+                    break 'scan;
+                }
+
+                // Hold back a `\r` until it is known not to end the line
+                if core::mem::replace(&mut carriage_return, c == '\r') && push('\r') {
+                    break 'scan;
+                }
+                if c != '\r' && push(c) {
+                    break 'scan;
+                }
+            }
+            reader.consume(used);
+        }
+
+        if empty {
+            return Err(ParseError::line("", "empty input").with_line(1).into());
+        }
+
+        // Like `find_marker`, report a marker at the first position if none is found
+        Ok(scanners.map(|s| s.marker.unwrap_or(s.n).to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -18,28 +219,75 @@ mod tests {
     #[test]
     fn test_example1() {
         let buffer = "bvwbjplbgvbhsrlpgdmjqwftvncz";
-        assert_eq!(find_marker(&buffer, 4), 5);
-        assert_eq!(find_marker(&buffer, 14), 23);
+        assert_eq!(find_marker(buffer, 4), 5);
+        assert_eq!(find_marker(buffer, 14), 23);
     }
 
     #[test]
     fn test_example2() {
         let buffer = "nppdvjthqldpwncqszvftbrmjlhg";
-        assert_eq!(find_marker(&buffer, 4), 6);
-        assert_eq!(find_marker(&buffer, 14), 23);
+        assert_eq!(find_marker(buffer, 4), 6);
+        assert_eq!(find_marker(buffer, 14), 23);
     }
 
     #[test]
     fn test_example3() {
         let buffer = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
-        assert_eq!(find_marker(&buffer, 4), 10);
-        assert_eq!(find_marker(&buffer, 14), 29);
+        assert_eq!(find_marker(buffer, 4), 10);
+        assert_eq!(find_marker(buffer, 14), 29);
     }
 
     #[test]
     fn test_example4() {
         let buffer = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
-        assert_eq!(find_marker(&buffer, 4), 11);
-        assert_eq!(find_marker(&buffer, 14), 26);
+        assert_eq!(find_marker(buffer, 4), 11);
+        assert_eq!(find_marker(buffer, 14), 26);
+    }
+
+    #[test]
+    fn test_stream() {
+        let buffer = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n";
+        let answers = Day6::stream(buffer.as_bytes(), &mut Skipped::new(true)).unwrap();
+        assert_eq!(answers, ["7", "19"]);
+
+        let mut scanner = MarkerScanner::new(4);
+        let ends: Vec<Option<usize>> = "aabcd".chars().map(|c| scanner.push(c)).collect();
+        assert_eq!(ends, [None, None, None, None, Some(5)]);
+    }
+
+    /// Answers from parsing the whole input, and from streaming it
+    fn both_ways(input: &str) -> ([String; 2], [String; 2]) {
+        let skipped = &mut Skipped::new(true);
+        let parsed = [false, true].map(|part2| Day6::solve(input, part2, skipped).unwrap());
+        let streamed = Day6::stream(input.as_bytes(), skipped).unwrap();
+
+        (parsed, streamed)
+    }
+
+    #[test]
+    fn test_stream_agrees() {
+        for input in [
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb\r\n",
+            "ab\rcd\r\nefgh",
+            "abc\r",
+            "ééaéböcdefghijklmnopq\n",
+            "aaaa",
+        ] {
+            let (parsed, streamed) = both_ways(input);
+            assert_eq!(parsed, streamed, "{:?}", input);
+        }
+
+        // A character split across reads is still decoded
+        let reader = std::io::BufReader::with_capacity(3, "aé€bcd".as_bytes());
+        let answers = Day6::stream(reader, &mut Skipped::new(true)).unwrap();
+        assert_eq!(answers[0], "4");
+
+        assert!(Day6::stream(&b"ab\xffcd"[..], &mut Skipped::new(true)).is_err());
+    }
+
+    #[test]
+    fn test_zero_length_marker() {
+        assert_eq!(find_marker("abc", 0), 0);
+        assert_eq!(MarkerScanner::new(0).push('a'), Some(0));
     }
 }
@@ -1,29 +1,42 @@
-use clap::Parser;
-use std::{
-    collections::HashMap,
-    error::Error,
-    fs::File,
-    io::{self, BufRead, BufReader},
-    rc::Rc,
-};
-
-enum FsNodeType {
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, rc::Rc};
+use aoc_common::{Error, ParseError, Skipped, Solution};
+use core::{convert::Infallible, fmt::Display};
+
+pub enum FsNodeType {
     Dir,
     File,
 }
 
-struct FsNode {
+/// A file or directory listed by `ls`
+// The filesystem model is not yet wired into `solve`
+#[allow(dead_code)]
+pub struct FsNode<'a> {
     kind: FsNodeType,
-    name: String,
+    name: &'a str,
     dsize: usize,
-    parent: Option<Rc<FsNode>>,
-    contents: Option<HashMap<String, Rc<FsNode>>>,
+    parent: Option<Rc<FsNode<'a>>>,
+    contents: Option<BTreeMap<&'a str, Rc<FsNode<'a>>>>,
 }
 
-fn parse_node(line: &str, curr_dir: Option<Rc<FsNode>>) -> FsNode {
+/// Parse a line of `ls` output: a file's size and name, or `dir` and a name
+pub fn parse_node<'a>(
+    line: &'a str,
+    curr_dir: Option<Rc<FsNode<'a>>>,
+) -> Result<FsNode<'a>, ParseError> {
     let mut data = line.split_whitespace();
-    let dtype = data.next().unwrap();
-    let name = data.next().unwrap();
+    let (dtype, name) = match (data.next(), data.next(), data.next()) {
+        (Some(dtype), Some(name), None) => (dtype, name),
+        _ => {
+            return Err(ParseError::line(
+                line,
+                "expected a size or \"dir\" and a name",
+            ))
+        }
+    };
 
     let kind = if line.starts_with("dir ") {
         FsNodeType::Dir
@@ -33,76 +46,78 @@ fn parse_node(line: &str, curr_dir: Option<Rc<FsNode>>) -> FsNode {
 
     let dsize = match kind {
         FsNodeType::Dir => 0,
-        FsNodeType::File => dtype.parse::<usize>().unwrap(),
+        FsNodeType::File => dtype
+            .parse::<usize>()
+            .map_err(|_| ParseError::at(line, dtype, "expected a file size"))?,
     };
 
     let contents = match kind {
-        FsNodeType::Dir => Some(HashMap::new()),
+        FsNodeType::Dir => Some(BTreeMap::new()),
         FsNodeType::File => None,
     };
 
-    FsNode {
-        kind: kind,
-        name: name.to_string(),
-        dsize: dsize,
+    Ok(FsNode {
+        kind,
+        name,
+        dsize,
         parent: curr_dir,
-        contents: contents,
-    }
+        contents,
+    })
 }
 
-#[derive(Parser, Debug)]
-pub struct Args {
-    #[arg(help = "Input file", id = "FILE", default_value = "-")]
-    fin: String,
+#[cfg(feature = "cli")]
+pub fn run(args: aoc_common::Args) -> Result<(), Error> {
+    let input = aoc_common::load_input(&args.fin)?;
+    let mut skipped = Skipped::new(args.strict);
+    println!("{}", Day7::solve(&input, args.part2, &mut skipped)?);
 
-    #[arg(long = "part2", help = "Use part2 logic", default_value_t = false)]
-    part2: bool,
+    skipped.report();
+
+    Ok(())
 }
 
-pub fn get_args() -> Result<Args, Box<dyn Error>> {
-    let args = Args::parse();
+pub struct Day7;
 
-    Ok(args)
-}
+impl Solution for Day7 {
+    // The filesystem is not modelled yet, so parsing never succeeds
+    type Input<'a> = Infallible;
 
-pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
-    let fin = open(&args.fin)?;
-    let mut lines = fin.lines().filter_map(|x| x.ok());
+    fn parse<'a>(_input: &'a str, _skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        Err(Error::Unsolved { day: 7 })
+    }
 
-    Ok(())
-}
+    fn part1(input: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(*input)
+    }
 
-fn open(filename: &str) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(
-            File::open(filename).map_err(|e| format!("{}: {}", filename, e))?,
-        ))),
+    fn part2(input: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(*input)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use std::matches;
 
     #[test]
     fn test_parse_node_dir() {
         let line = "dir abc";
-        let node = parse_node(&line, None);
+        let node = parse_node(line, None).unwrap();
 
         assert!(matches!(node.kind, FsNodeType::Dir));
         assert_eq!(node.name, "abc");
         assert_eq!(node.dsize, 0);
         assert!(node.parent.is_none());
         assert!(node.contents.is_some());
-        assert!(node.contents.unwrap().len() == 0);
+        assert!(node.contents.unwrap().is_empty());
     }
 
     #[test]
     fn test_parse_node_file() {
         let line = "123 def";
-        let node = parse_node(&line, None);
+        let node = parse_node(line, None).unwrap();
 
         assert!(matches!(node.kind, FsNodeType::File));
         assert_eq!(node.name, "def");
@@ -110,4 +125,41 @@ mod tests {
         assert!(node.parent.is_none());
         assert!(node.contents.is_none());
     }
+
+    #[test]
+    fn test_parse_node_invalid() {
+        let err = parse_node("12a def", None).err().unwrap();
+        assert_eq!(err.column, 1);
+        assert_eq!(err.text, "12a");
+
+        assert!(parse_node("dir", None).is_err());
+    }
+
+    /// A line of `ls` output: a file size (or none for a directory) and a name
+    fn listing() -> impl Strategy<Value = (Option<usize>, String)> {
+        (
+            prop::option::of(0..1_000_000usize),
+            "[a-z]{1,8}(\\.[a-z]{1,3})?",
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn parse_node_never_panics(line in "\\PC*") {
+            let _ = parse_node(&line, None);
+        }
+
+        #[test]
+        fn parse_node_round_trips((size, name) in listing()) {
+            let line = match size {
+                Some(size) => format!("{} {}", size, name),
+                None => format!("dir {}", name),
+            };
+            let node = parse_node(&line, None).unwrap();
+
+            prop_assert_eq!(node.name, name);
+            prop_assert_eq!(node.dsize, size.unwrap_or(0));
+            prop_assert_eq!(node.contents.is_some(), size.is_none());
+        }
+    }
 }
@@ -1,5 +1,5 @@
 fn main() {
-    if let Err(e) = day7::get_args().and_then(day7::run) {
+    if let Err(e) = aoc_common::get_args().and_then(day7::run) {
         eprintln!("{}", e);
         std::process::exit(1);
     }
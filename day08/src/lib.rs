@@ -1,46 +1,78 @@
-use clap::Parser;
-use std::{
-    error::Error,
-    fs::File,
-    io::{self, BufRead, BufReader},
-};
-
-#[derive(Parser, Debug)]
-pub struct Args {
-    #[arg(help = "Input file", id = "FILE", default_value = "-")]
-    fin: String,
-
-    #[arg(long = "part2", help = "Use part2 logic", default_value_t = false)]
-    part2: bool,
-}
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{format, vec, vec::Vec};
+use aoc_common::{parse_lines, Error, ParseError, Skipped, Solution};
+use core::fmt::Display;
+use tracing::trace;
+
+#[cfg(feature = "cli")]
+pub fn run(args: aoc_common::Args) -> Result<(), Error> {
+    let input = aoc_common::load_input(&args.fin)?;
+    let mut skipped = Skipped::new(args.strict);
+    println!("{}", Day8::solve(&input, args.part2, &mut skipped)?);
 
-pub fn get_args() -> Result<Args, Box<dyn Error>> {
-    let args = Args::parse();
+    skipped.report();
 
-    Ok(args)
+    Ok(())
 }
 
-pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
-    let fin = open(&args.fin)?;
-    let heights = fin
-        .lines()
-        .map(|line| line_to_values(line.unwrap()))
-        .collect::<Vec<_>>();
-
-    if args.part2 {
-        let answer = part2(heights)?;
-        println!("{}", answer);
-    } else {
-        let answer = part1(heights)?;
-        println!("{}", answer);
+pub struct Day8;
+
+impl Solution for Day8 {
+    /// Grid of tree heights
+    type Input<'a> = Vec<Vec<u32>>;
+
+    fn parse<'a>(input: &'a str, _skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        // Skipping a row would leave a grid that cannot be solved, so malformed
+        // rows are always errors
+        let heights = parse_lines(input, &mut Skipped::new(true), line_to_values)?;
+
+        // The solvers walk rows and columns of the same length
+        let n = heights.len();
+        for (i, (row, line)) in heights.iter().zip(input.lines()).enumerate() {
+            if row.len() != n {
+                let e = ParseError::line(line, format!("expected {} trees in a square grid", n));
+                return Err(e.with_line(i + 1).into());
+            }
+        }
+
+        Ok(heights)
     }
 
-    Ok(())
+    fn part1(heights: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(count_visible(heights))
+    }
+
+    fn part2(heights: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(max_scenic_score(heights))
+    }
 }
 
-fn part2(heights: Vec<Vec<u32>>) -> Result<u32, Box<dyn Error>> {
+/// Find the highest scenic score of any tree in the grid
+fn max_scenic_score(heights: &[Vec<u32>]) -> u32 {
     let mut max_score = 0;
+
+    for (i, row) in scenic_scores(heights).iter().enumerate() {
+        for (j, &score) in row.iter().enumerate() {
+            if score > max_score {
+                trace!(row = i, col = j, score, "best scenic score so far");
+                max_score = score;
+            }
+        }
+    }
+
+    max_score
+}
+
+/// Scenic score of each tree in the grid: the product of its viewing
+/// distances in each direction
+// Index-based loops read more clearly than iterators when walking the grid
+#[allow(clippy::needless_range_loop)]
+pub fn scenic_scores(heights: &[Vec<u32>]) -> Vec<Vec<u32>> {
     let n = heights.len();
+    let mut scores = vec![vec![0; n]; n];
 
     for i in 0..n {
         for j in 0..n {
@@ -85,13 +117,11 @@ fn part2(heights: Vec<Vec<u32>>) -> Result<u32, Box<dyn Error>> {
             }
             score *= view_dist;
 
-            if score > max_score {
-                max_score = score;
-            }
+            scores[i][j] = score;
         }
     }
 
-    Ok(max_score)
+    scores
 }
 
 pub fn viewing_dist(height: u32, others: &[u32]) -> u32 {
@@ -107,15 +137,25 @@ pub fn viewing_dist(height: u32, others: &[u32]) -> u32 {
     dist
 }
 
-fn part1(heights: Vec<Vec<u32>>) -> Result<u32, Box<dyn Error>> {
+/// Count the trees visible from outside the grid
+fn count_visible(heights: &[Vec<u32>]) -> u32 {
+    visibility(heights)
+        .iter()
+        .map(|row| row.iter().filter(|&&visible| visible).count() as u32)
+        .sum()
+}
+
+/// Whether each tree in the grid is visible from outside it
+#[allow(clippy::needless_range_loop)]
+pub fn visibility(heights: &[Vec<u32>]) -> Vec<Vec<bool>> {
     let n = heights.len();
-    let mut visibility = vec![vec![0; n]; n];
+    let mut visibility = vec![vec![false; n]; n];
 
     // set visible exterior
     for i in 0..n {
         for j in 0..n {
             if i == 0 || i == n - 1 || j == 0 || j == n - 1 {
-                visibility[i][j] = 1;
+                visibility[i][j] = true;
             }
         }
     }
@@ -127,7 +167,7 @@ fn part1(heights: Vec<Vec<u32>>) -> Result<u32, Box<dyn Error>> {
         for j in 1..n {
             if heights[i][j] > heights[i][idx] {
                 idx = j;
-                visibility[i][j] = 1;
+                visibility[i][j] = true;
             }
         }
         // and backwards
@@ -135,7 +175,7 @@ fn part1(heights: Vec<Vec<u32>>) -> Result<u32, Box<dyn Error>> {
         for j in (0..(n - 1)).rev() {
             if heights[i][j] > heights[i][idx] {
                 idx = j;
-                visibility[i][j] = 1;
+                visibility[i][j] = true;
             }
         }
     }
@@ -147,7 +187,7 @@ fn part1(heights: Vec<Vec<u32>>) -> Result<u32, Box<dyn Error>> {
         for i in 1..n {
             if heights[i][j] > heights[idx][j] {
                 idx = i;
-                visibility[i][j] = 1;
+                visibility[i][j] = true;
             }
         }
         // and backwards
@@ -155,34 +195,27 @@ fn part1(heights: Vec<Vec<u32>>) -> Result<u32, Box<dyn Error>> {
         for i in (0..(n - 1)).rev() {
             if heights[i][j] > heights[idx][j] {
                 idx = i;
-                visibility[i][j] = 1;
+                visibility[i][j] = true;
             }
         }
     }
 
-    let n_visible = visibility.iter().map(|x| x.iter().sum::<u32>()).sum();
-
-    Ok(n_visible)
+    visibility
 }
 
-fn line_to_values(line: String) -> Vec<u32> {
-    line.chars()
-        .filter_map(|x| x.to_digit(10))
-        .collect::<Vec<_>>()
-}
-
-fn open(filename: &str) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(
-            File::open(filename).map_err(|e| format!("{}: {}", filename, e))?,
-        ))),
-    }
+fn line_to_values(line: &str) -> Result<Vec<u32>, ParseError> {
+    line.char_indices()
+        .map(|(i, x)| {
+            x.to_digit(10)
+                .ok_or_else(|| ParseError::at(line, &line[i..i + x.len_utf8()], "expected a digit"))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_viewing_dist() {
@@ -192,4 +225,51 @@ mod tests {
         assert_eq!(viewing_dist(5, &others), 2);
         assert_eq!(viewing_dist(6, &others), 3);
     }
+
+    #[test]
+    fn test_parse_grid() {
+        assert_eq!(
+            Day8::parse("12\n34\n", &mut Skipped::new(false)).unwrap(),
+            vec![vec![1, 2], vec![3, 4]]
+        );
+
+        assert!(Day8::parse("12\n3\n", &mut Skipped::new(false)).is_err());
+        assert!(Day8::parse("1a\n34\n", &mut Skipped::new(false)).is_err());
+    }
+
+    #[test]
+    fn test_grids() {
+        let heights = vec![vec![9, 9, 9], vec![9, 1, 9], vec![9, 9, 9]];
+
+        let mut expected = vec![vec![true; 3]; 3];
+        expected[1][1] = false;
+        assert_eq!(visibility(&heights), expected);
+
+        assert_eq!(
+            scenic_scores(&heights),
+            vec![vec![0, 0, 0], vec![0, 1, 0], vec![0, 0, 0]]
+        );
+    }
+
+    /// A square grid of tree heights
+    fn grid() -> impl Strategy<Value = Vec<Vec<u32>>> {
+        (1..10usize).prop_flat_map(|n| prop::collection::vec(prop::collection::vec(0..10u32, n), n))
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "\\PC*(\n\\PC*)*") {
+            let _ = Day8::parse(&input, &mut Skipped::new(false));
+        }
+
+        #[test]
+        fn parse_round_trips(heights in grid()) {
+            let input: String = heights
+                .iter()
+                .map(|row| row.iter().map(|h| h.to_string()).collect::<String>() + "\n")
+                .collect();
+
+            prop_assert_eq!(Day8::parse(&input, &mut Skipped::new(true)).unwrap(), heights);
+        }
+    }
 }
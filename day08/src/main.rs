@@ -1,5 +1,5 @@
 fn main() {
-    if let Err(e) = day8::get_args().and_then(day8::run) {
+    if let Err(e) = aoc_common::get_args().and_then(day8::run) {
         eprintln!("{}", e);
         std::process::exit(1);
     }
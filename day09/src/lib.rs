@@ -0,0 +1,67 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use aoc_common::{each_line, Streaming};
+use aoc_common::{parse_lines, Error, Skipped, Solution};
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::{io::BufRead, string::String};
+
+use part2::{parse_line, Direction, Rope};
+
+pub mod part1;
+pub mod part2;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    /// Direction and distance of each motion of the head
+    type Input<'a> = Vec<(Direction, i32)>;
+
+    fn parse<'a>(input: &'a str, skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        parse_lines(input, skipped, parse_line)
+    }
+
+    /// Simulate a rope with two knots
+    fn part1(moves: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(part1::count_tail_positions(moves))
+    }
+
+    /// Simulate a rope with ten knots
+    fn part2(moves: &Self::Input<'_>) -> Result<impl Display, Error> {
+        let mut rope = Rope::default();
+
+        moves
+            .iter()
+            .for_each(|&(direction, dist)| rope.pull(direction, dist));
+
+        Ok(rope.tail_positions.len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Streaming for Day9 {
+    /// Pull both ropes as each motion is read. Memory grows with the number of
+    /// positions the tails visit, but not with the number of motions.
+    fn stream<R: BufRead>(reader: R, skipped: &mut Skipped) -> Result<[String; 2], Error> {
+        let mut short = part1::Rope::new();
+        let mut long = Rope::default();
+
+        each_line(reader, |i, line| {
+            match parse_line(line) {
+                Ok((direction, dist)) => {
+                    short.pull(direction, dist);
+                    long.pull(direction, dist);
+                }
+                Err(e) => skipped.skip(e.with_line(i))?,
+            }
+
+            Ok(())
+        })?;
+
+        Ok([short.tail_positions.len(), long.tail_positions.len()].map(|n| n.to_string()))
+    }
+}
@@ -0,0 +1,21 @@
+use aoc_common::{get_args, load_input, Args, Error, Skipped, Solution};
+use day9::Day9;
+
+fn main() {
+    if let Err(e) = get_args().and_then(run) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Parse input and apply movement logic
+fn run(args: Args) -> Result<(), Error> {
+    let input = load_input(&args.fin)?;
+    let mut skipped = Skipped::new(args.strict);
+
+    println!("{}", Day9::solve(&input, args.part2, &mut skipped)?);
+
+    skipped.report();
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use alloc::collections::BTreeSet;
+use tracing::trace;
+
+use crate::part2::Direction::{self, *};
+
+/// Pull a two-knot rope through the moves, counting positions visited by the tail
+pub fn count_tail_positions(moves: &[(Direction, i32)]) -> usize {
+    let mut rope = Rope::new();
+
+    for &(direction, dist) in moves {
+        rope.pull(direction, dist);
+    }
+
+    rope.tail_positions.len()
+}
+
+pub(crate) struct Rope {
+    head: [i32; 2],
+    tail: [i32; 2],
+    pub(crate) tail_positions: BTreeSet<[i32; 2]>,
+}
+
+impl Rope {
+    pub(crate) fn new() -> Self {
+        Rope {
+            head: [0, 0],
+            tail: [0, 0],
+            tail_positions: BTreeSet::new(),
+        }
+    }
+
+    pub(crate) fn pull(&mut self, direction: Direction, dist: i32) {
+        for _ in 0..dist {
+            self.move_head(direction);
+            self.tail_positions.insert(self.tail);
+        }
+    }
+
+    fn move_head(&mut self, direction: Direction) {
+        match direction {
+            Right => self.head[0] += 1,
+            Left => self.head[0] -= 1,
+            Up => self.head[1] += 1,
+            Down => self.head[1] -= 1,
+        };
+
+        if !self.is_touching() {
+            self.move_tail(direction);
+            trace!(knot = 1, x = self.tail[0], y = self.tail[1], "knot moved");
+        }
+    }
+
+    fn is_touching(&self) -> bool {
+        (self.head[0] - self.tail[0]).abs() <= 1 && (self.head[1] - self.tail[1]).abs() <= 1
+    }
+
+    fn move_tail(&mut self, direction: Direction) {
+        match direction {
+            Right => self.tail = [self.head[0] - 1, self.head[1]],
+            Left => self.tail = [self.head[0] + 1, self.head[1]],
+            Up => self.tail = [self.head[0], self.head[1] - 1],
+            Down => self.tail = [self.head[0], self.head[1] + 1],
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rope_is_touching() {
+        let mut rope = Rope {
+            head: [0, 0],
+            tail: [0, 0],
+            tail_positions: BTreeSet::new(),
+        };
+
+        for pos in [[0, 0], [0, 1], [1, 1], [-1, 0], [-1, -1], [-1, 1]] {
+            rope.head = pos;
+            assert!(rope.is_touching());
+        }
+
+        for pos in [[0, 2], [1, 2], [-1, 2], [-2, 1]] {
+            rope.head = pos;
+            assert!(!rope.is_touching());
+        }
+    }
+
+    #[test]
+    fn test_rope_move() {
+        let mut rope = Rope {
+            head: [0, 0],
+            tail: [0, 0],
+            tail_positions: BTreeSet::new(),
+        };
+
+        rope.pull(Right, 4);
+        assert_eq!(rope.head, [4, 0]);
+        assert_eq!(rope.tail, [3, 0]);
+
+        rope.pull(Up, 4);
+        assert_eq!(rope.head, [4, 4]);
+        assert_eq!(rope.tail, [4, 3]);
+
+        rope.pull(Left, 3);
+        assert_eq!(rope.head, [1, 4]);
+        assert_eq!(rope.tail, [2, 4]);
+
+        rope.pull(Down, 1);
+        assert_eq!(rope.head, [1, 3]);
+        assert_eq!(rope.tail, [2, 4]);
+
+        rope.pull(Right, 4);
+        assert_eq!(rope.head, [5, 3]);
+        assert_eq!(rope.tail, [4, 3]);
+
+        rope.pull(Down, 1);
+        assert_eq!(rope.head, [5, 2]);
+        assert_eq!(rope.tail, [4, 3]);
+
+        rope.pull(Left, 5);
+        assert_eq!(rope.head, [0, 2]);
+        assert_eq!(rope.tail, [1, 2]);
+
+        rope.pull(Right, 2);
+        assert_eq!(rope.head, [2, 2]);
+        assert_eq!(rope.tail, [1, 2]);
+    }
+}
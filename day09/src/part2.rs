@@ -1,6 +1,9 @@
-use std::{collections::HashSet, error::Error};
+use alloc::collections::BTreeSet;
+use aoc_common::ParseError;
+use tracing::trace;
 use Direction::*;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Direction {
     Up,
     Down,
@@ -9,26 +12,38 @@ pub enum Direction {
 }
 
 impl Direction {
-    fn new(dir: char) -> Direction {
+    /// Direction named by its initial: `U`, `D`, `L` or `R`
+    pub fn new(dir: &str) -> Option<Direction> {
         match dir {
-            'R' => Right,
-            'L' => Left,
-            'U' => Up,
-            'D' => Down,
-            _ => panic!("Unknown direction: {}", dir),
+            "R" => Some(Right),
+            "L" => Some(Left),
+            "U" => Some(Up),
+            "D" => Some(Down),
+            _ => None,
         }
     }
 }
 
 /// Parse line of input (tab-delimited direction (U/D/R/L) and distance)
-pub fn parse_line(
-    line: Result<String, std::io::Error>,
-) -> Result<(Direction, i32), Box<dyn Error>> {
-    let line_str = line?;
-    let mut data = line_str.split_whitespace().into_iter();
-    let dir_char = data.next().unwrap().chars().next().unwrap();
-    let direction = Direction::new(dir_char);
-    let distance = data.next().unwrap().parse::<i32>()?;
+pub fn parse_line(line: &str) -> Result<(Direction, i32), ParseError> {
+    let mut data = line.split_whitespace();
+    let (dir, dist) = match (data.next(), data.next(), data.next()) {
+        (Some(dir), Some(dist), None) => (dir, dist),
+        _ => {
+            return Err(ParseError::line(
+                line,
+                "expected a direction and a distance",
+            ))
+        }
+    };
+
+    let direction = Direction::new(dir)
+        .ok_or_else(|| ParseError::at(line, dir, "expected one of U, D, L or R"))?;
+    let distance = dist
+        .parse::<i32>()
+        .ok()
+        .filter(|&d| d >= 0)
+        .ok_or_else(|| ParseError::at(line, dist, "expected a distance"))?;
 
     Ok((direction, distance))
 }
@@ -36,7 +51,7 @@ pub fn parse_line(
 /// Track the current positions of all knots, and the positions the tail has been to.
 pub struct Rope {
     pub knots: [[i32; 2]; 10],
-    pub tail_positions: HashSet<[i32; 2]>,
+    pub tail_positions: BTreeSet<[i32; 2]>,
 }
 
 impl Rope {
@@ -89,7 +104,7 @@ impl Rope {
         let new_knot_pos = |prev_pos: i32, knot_pos: i32| -> i32 {
             let diff = knot_pos - prev_pos;
             match diff {
-                0 | 1 | -1 => prev_pos,
+                -1..=1 => prev_pos,
                 2 => prev_pos + 1,
                 -2 => prev_pos - 1,
                 _ => panic!("Invalid pair: {:?} {:?}", prev, knot),
@@ -98,18 +113,47 @@ impl Rope {
 
         self.knots[idx][0] = new_knot_pos(prev[0], knot[0]);
         self.knots[idx][1] = new_knot_pos(prev[1], knot[1]);
+        trace!(
+            knot = idx,
+            x = self.knots[idx][0],
+            y = self.knots[idx][1],
+            "knot moved"
+        );
+    }
+}
+
+/// A rope with every knot at the origin
+impl Default for Rope {
+    fn default() -> Rope {
+        Rope {
+            knots: [[0, 0]; 10],
+            tail_positions: BTreeSet::new(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert!(matches!(parse_line("R 4"), Ok((Right, 4))));
+
+        let err = parse_line("X 4").unwrap_err();
+        assert_eq!(err.column, 1);
+        assert_eq!(err.text, "X");
+
+        assert!(parse_line("U").is_err());
+        assert!(parse_line("U -2").is_err());
+    }
 
     #[test]
     fn test_rope_is_touching() {
         let mut rope = Rope {
             knots: [[0, 0]; 10],
-            tail_positions: HashSet::new(),
+            tail_positions: BTreeSet::new(),
         };
 
         for pos in [[0, 0], [0, 1], [1, 1], [-1, 0], [-1, -1], [-1, 1]] {
@@ -127,7 +171,7 @@ mod tests {
     fn test_rope_move() {
         let mut rope = Rope {
             knots: [[0, 0]; 10],
-            tail_positions: HashSet::new(),
+            tail_positions: BTreeSet::new(),
         };
 
         rope.pull(Right, 4);
@@ -174,4 +218,24 @@ mod tests {
         assert_eq!(rope.knots[0], [2, 2]);
         assert_eq!(rope.knots[1], [1, 2]);
     }
+
+    /// A motion of the head: a direction letter and distance
+    fn motion() -> impl Strategy<Value = (char, i32)> {
+        (prop::sample::select(vec!['U', 'D', 'L', 'R']), 0..100i32)
+    }
+
+    proptest! {
+        #[test]
+        fn parse_line_never_panics(line in "\\PC*") {
+            let _ = parse_line(&line);
+        }
+
+        #[test]
+        fn parse_line_round_trips((letter, dist) in motion()) {
+            let (direction, distance) = parse_line(&format!("{} {}", letter, dist)).unwrap();
+
+            prop_assert_eq!(Direction::new(&letter.to_string()), Some(direction));
+            prop_assert_eq!(distance, dist);
+        }
+    }
 }
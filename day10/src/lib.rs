@@ -1,21 +1,53 @@
-use std::error::Error;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use aoc_common::{each_line, Streaming};
+use aoc_common::{Error, ParseError, Skipped, Solution};
+use core::fmt::{self, Display};
+#[cfg(feature = "std")]
+use std::io::BufRead;
+use tracing::{debug, trace};
 use Instruction::*;
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Instruction {
     NoOp,
-    AddX(isize),
+    AddX(i64),
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoOp => write!(f, "noop"),
+            AddX(val) => write!(f, "addx {}", val),
+        }
+    }
 }
 
 /// Parse line of input
-pub fn parse_line(line: String) -> Result<Instruction, Box<dyn Error>> {
+pub fn parse_line(line: &str) -> Result<Instruction, ParseError> {
     let mut data = line.split_whitespace();
-    let op_name = data.next();
 
-    match op_name {
-        Some("noop") => Ok(NoOp),
-        Some("addx") => Ok(AddX(data.next().unwrap().parse::<isize>()?)),
-        _ => Err("Invalid operation".into()),
+    match (data.next(), data.next(), data.next()) {
+        (Some("noop"), None, _) => Ok(NoOp),
+        (Some("addx"), Some(val), None) => val
+            .parse::<i64>()
+            .map(AddX)
+            .map_err(|_| ParseError::at(line, val, "expected a number")),
+        (Some(op @ ("noop" | "addx")), _, _) => Err(ParseError::at(
+            line,
+            op,
+            format!("wrong number of operands for {}", op),
+        )),
+        (Some(op), _, _) => Err(ParseError::at(line, op, "unknown operation")),
+        (None, _, _) => Err(ParseError::line(line, "missing operation")),
     }
 }
 
@@ -23,39 +55,59 @@ pub fn parse_line(line: String) -> Result<Instruction, Box<dyn Error>> {
 /// Also, store the "interesting" cycles and track the cumulative strength
 /// observed at these cycles.
 pub struct CPU {
-    pub register_x: isize,
+    pub register_x: i64,
     pub cycle_count: usize,
     pub interesting_cycles: Vec<usize>,
-    pub interesting_strength: isize,
+    pub interesting_strength: i64,
     pub crt: CRT,
 }
 
+/// Error for a register or signal strength which no longer fits in an `i64`
+const OVERFLOW: Error = Error::Overflow {
+    day: 10,
+    width: "i64",
+};
+
 impl CPU {
-    /// Execute an instruction
-    pub fn execute(&mut self, instr: Instruction) {
+    /// Execute an instruction, failing if the register or signal strength
+    /// overflows
+    pub fn execute(&mut self, instr: Instruction) -> Result<(), Error> {
         match instr {
             AddX(val) => {
-                self.increment_cycles(2);
-                self.register_x += val;
+                self.increment_cycles(2)?;
+                self.register_x = self.register_x.checked_add(val).ok_or(OVERFLOW)?;
             }
-            NoOp => self.increment_cycles(1),
+            NoOp => self.increment_cycles(1)?,
         }
+
+        Ok(())
     }
 
     /// Tick a number of cycles, adding "interesting" strength when appropriate.
-    fn increment_cycles(&mut self, n_cycles: usize) {
+    fn increment_cycles(&mut self, n_cycles: usize) -> Result<(), Error> {
         for _ in 0..n_cycles {
             self.cycle_count += 1;
+            trace!(cycle = self.cycle_count, x = self.register_x, "tick");
 
             // TODO: track next interesting cycle and do equality comparison
             if self.interesting_cycles.contains(&self.cycle_count) {
-                self.interesting_strength += (self.cycle_count as isize) * self.register_x;
+                let strength = (self.cycle_count as i64)
+                    .checked_mul(self.register_x)
+                    .ok_or(OVERFLOW)?;
+                debug!(cycle = self.cycle_count, strength, "interesting cycle");
+                self.interesting_strength = self
+                    .interesting_strength
+                    .checked_add(strength)
+                    .ok_or(OVERFLOW)?;
             }
 
-            self.crt.draw(self.cycle_count, self.register_x as usize);
+            self.crt.draw(self.cycle_count, self.register_x);
         }
+
+        Ok(())
     }
 
+    #[cfg(feature = "std")]
     pub fn display(&self) {
         self.crt.print();
     }
@@ -82,22 +134,34 @@ pub struct CRT {
 }
 
 impl CRT {
-    fn draw(&mut self, cycle: usize, sprite_pos: usize) {
+    fn draw(&mut self, cycle: usize, sprite_pos: i64) {
         let x = (cycle - 1) % CRT_DISPLAY_WIDTH;
         let y = (cycle - 1) / CRT_DISPLAY_WIDTH;
 
-        let dist = (sprite_pos as isize) - (x as isize);
-        if dist.abs() <= 1 {
+        // Programs may run for longer than it takes to draw the screen
+        if y >= CRT_DISPLAY_HEIGHT {
+            return;
+        }
+
+        if sprite_pos.abs_diff(x as i64) <= 1 {
             self.display[y][x] = '#';
         } else {
             self.display[y][x] = '.';
         }
     }
 
+    #[cfg(feature = "std")]
     fn print(&self) {
-        for row in self.display {
-            println!("{}", row.into_iter().collect::<String>());
-        }
+        println!("{}", self.render());
+    }
+
+    /// Render the display as rows of text
+    pub fn render(&self) -> String {
+        self.display
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
@@ -109,17 +173,175 @@ impl Default for CRT {
     }
 }
 
+/// Error for the instruction on `line` (whose text is `text`) overflowing the
+/// register or signal strength
+fn overflow_at(line: usize, text: &str) -> Error {
+    ParseError::line(text, "register or signal strength overflows i64")
+        .with_line(line)
+        .into()
+}
+
+/// Parse a program, numbering each instruction by the line it is on
+pub fn parse_program(
+    input: &str,
+    skipped: &mut Skipped,
+) -> Result<Vec<(usize, Instruction)>, Error> {
+    let mut program = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        match parse_line(line) {
+            Ok(instruction) => program.push((i + 1, instruction)),
+            Err(e) => skipped.skip(e.with_line(i + 1))?,
+        }
+    }
+
+    Ok(program)
+}
+
+/// Run a program to completion, failing at the first instruction to overflow
+pub fn run_program(program: &[(usize, Instruction)]) -> Result<CPU, Error> {
+    let mut cpu = CPU {
+        ..Default::default()
+    };
+
+    for &(line, instruction) in program {
+        cpu.execute(instruction)
+            .map_err(|_| overflow_at(line, &instruction.to_string()))?;
+    }
+
+    Ok(cpu)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    /// Instructions, with the number of the line each is on
+    type Input<'a> = Vec<(usize, Instruction)>;
+
+    fn parse<'a>(input: &'a str, skipped: &mut Skipped) -> Result<Self::Input<'a>, Error> {
+        parse_program(input, skipped)
+    }
+
+    /// Report the cumulative signal strength
+    fn part1(program: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(run_program(program)?.interesting_strength)
+    }
+
+    /// Report the image drawn on the CRT
+    fn part2(program: &Self::Input<'_>) -> Result<impl Display, Error> {
+        Ok(run_program(program)?.crt.render())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Streaming for Day10 {
+    /// Execute each instruction as it is read
+    fn stream<R: BufRead>(reader: R, skipped: &mut Skipped) -> Result<[String; 2], Error> {
+        let mut cpu = CPU {
+            ..Default::default()
+        };
+
+        each_line(reader, |i, line| {
+            match parse_line(line) {
+                Ok(instruction) => cpu.execute(instruction).map_err(|_| overflow_at(i, line))?,
+                Err(e) => skipped.skip(e.with_line(i))?,
+            }
+
+            Ok(())
+        })?;
+
+        Ok([cpu.interesting_strength.to_string(), cpu.crt.render()])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use std::matches;
 
     #[test]
     fn test_parse_line() {
-        let instr = parse_line("noop".to_string());
+        let instr = parse_line("noop");
         assert!(matches!(instr, Ok(NoOp)));
 
-        let instr = parse_line("addx 10".to_string());
+        let instr = parse_line("addx 10");
         assert!(matches!(instr, Ok(AddX(10))));
+
+        let err = parse_line("addx ten").unwrap_err();
+        assert_eq!(err.column, 6);
+        assert_eq!(err.text, "ten");
+
+        assert!(parse_line("addx").is_err());
+        assert!(parse_line("subx 3").is_err());
+    }
+
+    #[test]
+    fn test_long_program() {
+        let program: Vec<_> = (1..=300).map(|i| (i, NoOp)).collect();
+        let cpu = run_program(&program).unwrap();
+
+        assert_eq!(cpu.cycle_count, 300);
+        assert_eq!(cpu.crt.render().lines().count(), CRT_DISPLAY_HEIGHT);
+    }
+
+    /// Line on which running a program overflowed, if it did
+    fn overflow_line(input: &str) -> Option<usize> {
+        let program = parse_program(input, &mut Skipped::new(true)).unwrap();
+        match Day10::part1(&program).map(|answer| answer.to_string()) {
+            Err(Error::Parse(e)) => Some(e.line),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = format!("noop\naddx {}\naddx 1\n", i64::MAX - 1);
+        assert_eq!(overflow_line(&input), Some(3));
+
+        // Strength at cycle 20 is 20 times the register, which the tenth
+        // instruction is still adding to
+        let addx = format!("addx {}\n", i64::MAX / 10);
+        assert_eq!(overflow_line(&addx.repeat(9)), None);
+        assert_eq!(overflow_line(&addx.repeat(10)), Some(10));
+
+        let program = [(1, AddX(i64::MIN + 1)), (2, NoOp)];
+        assert_eq!(run_program(&program).unwrap().register_x, i64::MIN + 2);
+    }
+
+    #[test]
+    fn test_stream_overflow() {
+        let input = format!("noop\n\naddx {}\naddx 1\n", i64::MAX - 1);
+        match Day10::stream(input.as_bytes(), &mut Skipped::new(false)) {
+            Err(Error::Parse(e)) => assert_eq!((e.line, e.text.as_str()), (4, "addx 1")),
+            _ => panic!("expected the overflow to be reported"),
+        }
+    }
+
+    /// An instruction of the CPU's program
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        prop_oneof![Just(NoOp), any::<i64>().prop_map(AddX)]
+    }
+
+    proptest! {
+        #[test]
+        fn parse_line_never_panics(line in "\\PC*") {
+            let _ = parse_line(&line);
+        }
+
+        #[test]
+        fn parse_line_round_trips(instr in instruction()) {
+            let line = match instr {
+                NoOp => "noop".to_string(),
+                AddX(val) => format!("addx {}", val),
+            };
+
+            prop_assert_eq!(parse_line(&line).unwrap(), instr);
+        }
+
+        #[test]
+        fn run_program_never_panics(program in prop::collection::vec(instruction(), 0..150)) {
+            let program: Vec<_> = program.into_iter().enumerate().collect();
+            let _ = run_program(&program);
+        }
     }
 }
@@ -1,11 +1,5 @@
-use clap::Parser;
-use std::{
-    error::Error,
-    fs::File,
-    io::{self, BufRead, BufReader},
-};
-
-use day10::{parse_line, CPU};
+use aoc_common::{get_args, load_input, Args, Error, Skipped, Solution};
+use day10::Day10;
 
 fn main() {
     if let Err(e) = get_args().and_then(run) {
@@ -15,43 +9,13 @@ fn main() {
 }
 
 /// Parse input and apply logic
-pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
-    let mut cpu = CPU {
-        ..Default::default()
-    };
-
-    let fin = open(&args.fin)?;
-    fin.lines()
-        .filter_map(|x| parse_line(x.ok().unwrap()).ok()) // TODO: is there a cleaner way to unpack the Result lines?
-        .for_each(|instruction| cpu.execute(instruction));
+fn run(args: Args) -> Result<(), Error> {
+    let input = load_input(&args.fin)?;
+    let mut skipped = Skipped::new(args.strict);
 
-    println!("{}", cpu.interesting_strength);
+    println!("{}", Day10::solve(&input, args.part2, &mut skipped)?);
 
-    cpu.display();
+    skipped.report();
 
     Ok(())
 }
-
-#[derive(Parser, Debug)]
-pub struct Args {
-    #[arg(help = "Input file", id = "FILE", default_value = "-")]
-    fin: String,
-
-    #[arg(long = "part2", help = "Use part2 logic", default_value_t = false)]
-    part2: bool,
-}
-
-pub fn get_args() -> Result<Args, Box<dyn Error>> {
-    let args = Args::parse();
-
-    Ok(args)
-}
-
-fn open(filename: &str) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(
-            File::open(filename).map_err(|e| format!("{}: {}", filename, e))?,
-        ))),
-    }
-}
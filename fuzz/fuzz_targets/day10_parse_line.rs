@@ -0,0 +1,14 @@
+#![no_main]
+
+use day10::Instruction;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|line: &str| {
+    if let Ok(instr) = day10::parse_line(line) {
+        let formatted = match instr {
+            Instruction::NoOp => "noop".to_string(),
+            Instruction::AddX(val) => format!("addx {}", val),
+        };
+        assert_eq!(day10::parse_line(&formatted).unwrap(), instr);
+    }
+});
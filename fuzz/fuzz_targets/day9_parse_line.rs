@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|line: &str| {
+    if let Ok((_, distance)) = day9::part2::parse_line(line) {
+        assert!(distance >= 0);
+    }
+});
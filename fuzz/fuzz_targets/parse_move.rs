@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|line: &str| {
+    if let Ok(mv) = day5::parse_move(line) {
+        let formatted = format!("move {} from {} to {}", mv.n, mv.src + 1, mv.dst + 1);
+        let reparsed = day5::parse_move(&formatted).unwrap();
+        assert_eq!(
+            (reparsed.n, reparsed.src, reparsed.dst),
+            (mv.n, mv.src, mv.dst)
+        );
+    }
+});
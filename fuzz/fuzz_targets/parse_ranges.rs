@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fn check(line: &str) {
+    if let Ok(ranges) = day4::parse_ranges(line) {
+        assert_eq!(ranges.len(), 2);
+        assert!(ranges.iter().all(|r| r.start < r.end));
+    }
+}
+
+fuzz_target!(|input: (&str, [i32; 4])| {
+    let (line, [a, b, c, d]) = input;
+    check(line);
+
+    // Random text rarely reaches the bounds of the section IDs
+    check(&format!("{}-{},{}-{}", a, b, c, d));
+});
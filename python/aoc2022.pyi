@@ -0,0 +1,73 @@
+"""Advent of Code 2022 solvers, for exploring puzzle variants"""
+
+def parse_ranges(line: str) -> tuple[range, range]:
+    """Parse a pair of section assignments like `2-4,6-8` into `range`s"""
+
+def either_contains(r1: range, r2: range) -> bool:
+    """Whether either range of sections contains the other"""
+
+def overlaps(r1: range, r2: range) -> bool:
+    """Whether two ranges of sections overlap"""
+
+class Procedure:
+    """Stacks of crates, and the moves a crane makes between them"""
+
+    def __init__(self, input: str, strict: bool = False) -> None: ...
+    @property
+    def stacks(self) -> list[str]:
+        """Crates in each stack, from top to bottom"""
+    @property
+    def moves(self) -> list[tuple[int, int, int]]:
+        """Each move as (crates, from stack, to stack), numbering stacks from 1"""
+    def rearrange(self, as_stack: bool = False) -> str:
+        """Apply the moves, and report the top crate of each stack"""
+
+def find_marker(buffer: str, n: int) -> int:
+    """Number of characters read when the first `n` distinct characters in a
+    row have been seen"""
+
+class Forest:
+    """Square grid of tree heights"""
+
+    def __init__(self, input: str) -> None: ...
+    @property
+    def heights(self) -> list[list[int]]: ...
+    def visibility(self) -> list[list[bool]]:
+        """Whether each tree is visible from outside the grid"""
+    def scenic_scores(self) -> list[list[int]]:
+        """Scenic score of each tree"""
+
+class Rope:
+    """Rope of ten knots, pulled by its head"""
+
+    def __init__(self) -> None: ...
+    def pull(self, direction: str, distance: int) -> None:
+        """Pull the head of the rope a distance in a direction: `U`, `D`, `L`
+        or `R`"""
+    @property
+    def knots(self) -> list[tuple[int, int]]:
+        """Position of each knot, from the head to the tail"""
+    @property
+    def tail_positions(self) -> set[tuple[int, int]]:
+        """Positions the tail has visited"""
+
+class CPU:
+    """CPU with a single register, drawing on a CRT as it runs"""
+
+    def __init__(self) -> None: ...
+    def execute(self, instruction: str) -> None:
+        """Execute an instruction, like `addx 3` or `noop`, failing if the
+        register overflows"""
+    def run(self, program: str) -> None:
+        """Execute each line of a program, failing before running any of it if
+        a line is malformed, or part way through if the register overflows"""
+    @property
+    def register_x(self) -> int: ...
+    @property
+    def cycle_count(self) -> int: ...
+    @property
+    def interesting_strength(self) -> int:
+        """Sum of the signal strengths at the interesting cycles so far"""
+    @property
+    def crt(self) -> str:
+        """Image on the CRT, as rows of text"""
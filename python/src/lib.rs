@@ -0,0 +1,254 @@
+//! Python bindings for some of the day libraries, for exploring puzzle
+//! variants from notebooks. Build the module into the current virtualenv and
+//! test it with
+//!
+//! ```sh
+//! cd python
+//! maturin develop
+//! pytest
+//! ```
+
+use aoc_common::{parse_lines, Skipped, Solution};
+use day10::CPU;
+use day5::Procedure;
+use day9::part2::{Direction, Rope};
+use pyo3::{
+    exceptions::{PyOverflowError, PyValueError},
+    prelude::*,
+    types::{PyRange, PyRangeMethods},
+};
+use std::{collections::BTreeSet, ops::Range};
+
+/// Raise invalid input as a `ValueError`
+fn value_error(e: impl ToString) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Raise arithmetic which overflows as an `OverflowError`
+fn overflow_error(e: impl ToString) -> PyErr {
+    PyOverflowError::new_err(e.to_string())
+}
+
+fn to_range<'py>(py: Python<'py>, r: &Range<i32>) -> PyResult<Bound<'py, PyRange>> {
+    PyRange::new(py, r.start as isize, r.end as isize)
+}
+
+/// Sections covered by a Python `range`, which must be non-empty and count up
+/// one section at a time
+fn from_range(r: &Bound<'_, PyRange>) -> PyResult<Range<i32>> {
+    if r.step()? != 1 || r.start()? >= r.stop()? {
+        return Err(value_error("expected a non-empty range with a step of 1"));
+    }
+
+    Ok(i32::try_from(r.start()?)?..i32::try_from(r.stop()?)?)
+}
+
+/// Parse a pair of section assignments like `2-4,6-8` into `range`s
+#[pyfunction]
+fn parse_ranges<'py>(
+    py: Python<'py>,
+    line: &str,
+) -> PyResult<(Bound<'py, PyRange>, Bound<'py, PyRange>)> {
+    let ranges = day4::parse_ranges(line).map_err(value_error)?;
+
+    Ok((to_range(py, &ranges[0])?, to_range(py, &ranges[1])?))
+}
+
+/// Whether either range of sections contains the other
+#[pyfunction]
+fn either_contains(r1: &Bound<'_, PyRange>, r2: &Bound<'_, PyRange>) -> PyResult<bool> {
+    Ok(day4::either_contains(&from_range(r1)?, &from_range(r2)?))
+}
+
+/// Whether two ranges of sections overlap
+#[pyfunction]
+fn overlaps(r1: &Bound<'_, PyRange>, r2: &Bound<'_, PyRange>) -> PyResult<bool> {
+    Ok(day4::overlaps(&from_range(r1)?, &from_range(r2)?))
+}
+
+/// Stacks of crates, and the moves a crane makes between them
+#[pyclass(name = "Procedure", frozen)]
+struct PyProcedure(Procedure);
+
+#[pymethods]
+impl PyProcedure {
+    /// Parse the crate diagram and the moves which follow it, skipping
+    /// malformed moves unless strict
+    #[new]
+    #[pyo3(signature = (input, strict = false))]
+    fn new(input: &str, strict: bool) -> PyResult<Self> {
+        day5::parse_procedure(input, &mut Skipped::new(strict))
+            .map(PyProcedure)
+            .map_err(value_error)
+    }
+
+    /// Crates in each stack, from top to bottom
+    #[getter]
+    fn stacks(&self) -> Vec<String> {
+        self.0
+            .stacks
+            .iter()
+            .map(|stack| stack.iter().collect())
+            .collect()
+    }
+
+    /// Each move as (crates, from stack, to stack), numbering stacks from 1
+    #[getter]
+    fn moves(&self) -> Vec<(usize, usize, usize)> {
+        self.0
+            .moves
+            .iter()
+            .map(|mv| (mv.n, mv.src + 1, mv.dst + 1))
+            .collect()
+    }
+
+    /// Apply the moves, and report the top crate of each stack. The
+    /// CrateMover 9000 moves crates one at a time, the 9001 as a stack.
+    #[pyo3(signature = (as_stack = false))]
+    fn rearrange(&self, as_stack: bool) -> String {
+        day5::rearrange(&self.0, as_stack)
+    }
+}
+
+/// Number of characters read when the first `n` distinct characters in a row
+/// have been seen
+#[pyfunction]
+fn find_marker(buffer: &str, n: usize) -> PyResult<usize> {
+    match n {
+        0 => Err(value_error("markers are at least one character long")),
+        _ => Ok(day6::find_marker(buffer, n)),
+    }
+}
+
+/// Square grid of tree heights
+#[pyclass(frozen)]
+struct Forest {
+    heights: Vec<Vec<u32>>,
+}
+
+#[pymethods]
+impl Forest {
+    /// Parse rows of digits into a grid
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        let heights = day8::Day8::parse(input, &mut Skipped::new(true)).map_err(value_error)?;
+
+        Ok(Forest { heights })
+    }
+
+    #[getter]
+    fn heights(&self) -> Vec<Vec<u32>> {
+        self.heights.clone()
+    }
+
+    /// Whether each tree is visible from outside the grid
+    fn visibility(&self) -> Vec<Vec<bool>> {
+        day8::visibility(&self.heights)
+    }
+
+    /// Scenic score of each tree
+    fn scenic_scores(&self) -> Vec<Vec<u32>> {
+        day8::scenic_scores(&self.heights)
+    }
+}
+
+/// Rope of ten knots, pulled by its head
+#[pyclass(name = "Rope")]
+struct PyRope(Rope);
+
+#[pymethods]
+impl PyRope {
+    /// A rope with every knot at the origin
+    #[new]
+    fn new() -> Self {
+        PyRope(Rope::default())
+    }
+
+    /// Pull the head of the rope a distance in a direction: `U`, `D`, `L` or
+    /// `R`
+    fn pull(&mut self, direction: &str, distance: i32) -> PyResult<()> {
+        let direction = Direction::new(direction)
+            .ok_or_else(|| value_error(format!("unknown direction {:?}", direction)))?;
+        if distance < 0 {
+            return Err(value_error("distances can't be negative"));
+        }
+
+        self.0.pull(direction, distance);
+        Ok(())
+    }
+
+    /// Position of each knot, from the head to the tail
+    #[getter]
+    fn knots(&self) -> Vec<(i32, i32)> {
+        self.0.knots.iter().map(|&[x, y]| (x, y)).collect()
+    }
+
+    /// Positions the tail has visited
+    #[getter]
+    fn tail_positions(&self) -> BTreeSet<(i32, i32)> {
+        self.0.tail_positions.iter().map(|&[x, y]| (x, y)).collect()
+    }
+}
+
+/// CPU with a single register, drawing on a CRT as it runs
+#[pyclass(name = "CPU")]
+struct PyCpu(CPU);
+
+#[pymethods]
+impl PyCpu {
+    #[new]
+    fn new() -> Self {
+        PyCpu(CPU::default())
+    }
+
+    /// Execute an instruction, like `addx 3` or `noop`, failing if the
+    /// register overflows
+    fn execute(&mut self, instruction: &str) -> PyResult<()> {
+        let instruction = day10::parse_line(instruction).map_err(value_error)?;
+
+        self.0.execute(instruction).map_err(overflow_error)
+    }
+
+    /// Execute each line of a program, failing before running any of it if
+    /// a line is malformed, or part way through if the register overflows
+    fn run(&mut self, program: &str) -> PyResult<()> {
+        let program = parse_lines(program, &mut Skipped::new(true), day10::parse_line)
+            .map_err(value_error)?;
+
+        for instruction in program {
+            self.0.execute(instruction).map_err(overflow_error)?;
+        }
+        Ok(())
+    }
+
+    #[getter]
+    fn register_x(&self) -> i64 {
+        self.0.register_x
+    }
+
+    #[getter]
+    fn cycle_count(&self) -> usize {
+        self.0.cycle_count
+    }
+
+    /// Sum of the signal strengths at the interesting cycles so far
+    #[getter]
+    fn interesting_strength(&self) -> i64 {
+        self.0.interesting_strength
+    }
+
+    /// Image on the CRT, as rows of text
+    #[getter]
+    fn crt(&self) -> String {
+        self.0.crt.render()
+    }
+}
+
+/// Advent of Code 2022 solvers, for exploring puzzle variants
+#[pymodule]
+mod aoc2022 {
+    #[pymodule_export]
+    use super::{
+        either_contains, find_marker, overlaps, parse_ranges, Forest, PyCpu, PyProcedure, PyRope,
+    };
+}
@@ -0,0 +1,108 @@
+"""Check the bindings against the example inputs and answers of each day"""
+
+import tomllib
+from pathlib import Path
+
+import pytest
+
+import aoc2022
+
+ROOT = Path(__file__).parents[2]
+
+
+def example(day: int, name: str = "example") -> str:
+    return (ROOT / f"day{day:02}" / f"{name}.txt").read_text()
+
+
+def answers(day: int, name: str = "example") -> dict[str, str]:
+    with open(ROOT / f"day{day:02}" / "answers.toml", "rb") as f:
+        return tomllib.load(f)[name]
+
+
+def test_day04():
+    pairs = [aoc2022.parse_ranges(line) for line in example(4).splitlines()]
+    assert pairs[0] == (range(2, 5), range(6, 9))
+
+    expected = answers(4)
+    assert str(sum(aoc2022.either_contains(*pair) for pair in pairs)) == expected["part1"]
+    assert str(sum(aoc2022.overlaps(*pair) for pair in pairs)) == expected["part2"]
+
+    with pytest.raises(ValueError):
+        aoc2022.parse_ranges("2-4")
+    with pytest.raises(ValueError):
+        aoc2022.overlaps(range(2, 8, 2), range(3, 5))
+
+
+def test_day05():
+    procedure = aoc2022.Procedure(example(5))
+    assert procedure.stacks == ["NZ", "DCM", "P"]
+    assert procedure.moves[0] == (1, 2, 1)
+
+    expected = answers(5)
+    assert procedure.rearrange() == expected["part1"]
+    assert procedure.rearrange(as_stack=True) == expected["part2"]
+
+    bad_move = example(5) + "move 9 from 1 to 2\n"
+    assert len(aoc2022.Procedure(bad_move).moves) == len(procedure.moves)
+    with pytest.raises(ValueError):
+        aoc2022.Procedure(bad_move, strict=True)
+
+
+def test_day06():
+    # The puzzle gives several short examples rather than an example file
+    assert aoc2022.find_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4) == 7
+    assert aoc2022.find_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14) == 19
+
+    with pytest.raises(ValueError):
+        aoc2022.find_marker("abc", 0)
+
+
+def test_day08():
+    forest = aoc2022.Forest(example(8))
+    assert forest.heights[0] == [3, 0, 3, 7, 3]
+
+    expected = answers(8)
+    visible = sum(row.count(True) for row in forest.visibility())
+    assert str(visible) == expected["part1"]
+    assert str(max(max(row) for row in forest.scenic_scores())) == expected["part2"]
+
+    with pytest.raises(ValueError):
+        aoc2022.Forest("12\n3x\n")
+
+
+@pytest.mark.parametrize("name", ["example", "example2"])
+def test_day09(name):
+    rope = aoc2022.Rope()
+    for line in example(9, name).splitlines():
+        direction, distance = line.split()
+        rope.pull(direction, int(distance))
+
+    assert len(rope.knots) == 10
+    assert str(len(rope.tail_positions)) == answers(9, name)["part2"]
+
+    with pytest.raises(ValueError):
+        rope.pull("X", 1)
+    with pytest.raises(ValueError):
+        rope.pull("U", -1)
+
+
+def test_day10():
+    cpu = aoc2022.CPU()
+    cpu.execute("addx 3")
+    assert (cpu.register_x, cpu.cycle_count) == (4, 2)
+
+    cpu = aoc2022.CPU()
+    cpu.run(example(10))
+
+    expected = answers(10)
+    assert str(cpu.interesting_strength) == expected["part1"]
+    assert cpu.crt == expected["part2"]
+
+    with pytest.raises(ValueError):
+        cpu.run("noop\nsubx 3\n")
+    assert cpu.cycle_count == 240
+
+    cpu = aoc2022.CPU()
+    cpu.execute(f"addx {2**63 - 2}")
+    with pytest.raises(OverflowError):
+        cpu.execute("addx 1")
@@ -0,0 +1,111 @@
+//! WebAssembly facade over the day libraries, for solving puzzles in a
+//! browser. Build it and generate its JavaScript bindings with
+//!
+//! ```sh
+//! cargo build --package aoc-wasm --release --target wasm32-unknown-unknown
+//! wasm-bindgen --target web --out-dir wasm/www/pkg \
+//!     target/wasm32-unknown-unknown/release/aoc_wasm.wasm
+//! ```
+//!
+//! then serve the playground with `python3 -m http.server -d wasm/www` and
+//! open it in a browser.
+
+use aoc_common::{Error, Skipped, Solution};
+use wasm_bindgen::prelude::*;
+
+/// Days with a solution that can be run in the browser
+pub const DAYS: [u8; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+/// Answers to both parts of a puzzle, and a report of any malformed lines
+/// skipped while parsing it (empty if there were none)
+#[wasm_bindgen(getter_with_clone)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+    pub skipped: String,
+}
+
+/// Days that can be solved
+#[wasm_bindgen]
+pub fn days() -> Vec<u8> {
+    DAYS.to_vec()
+}
+
+/// Solve both parts of a day's puzzle, skipping malformed lines unless strict
+#[wasm_bindgen]
+pub fn solve(day: u8, input: &str, strict: bool) -> Result<Answers, JsError> {
+    let mut skipped = Skipped::new(strict);
+    let [part1, part2] = run(day, input, &mut skipped).map_err(|e| JsError::new(&e.to_string()))?;
+
+    let skipped = match skipped.is_empty() {
+        true => String::new(),
+        false => skipped.to_string(),
+    };
+
+    Ok(Answers {
+        part1,
+        part2,
+        skipped,
+    })
+}
+
+/// Solve both parts of a day's puzzle
+pub fn run(day: u8, input: &str, skipped: &mut Skipped) -> Result<[String; 2], Error> {
+    match day {
+        1 => solve_both::<day1::Day1>(input, skipped),
+        2 => solve_both::<day2::Day2>(input, skipped),
+        3 => solve_both::<day3::Day3>(input, skipped),
+        4 => solve_both::<day4::Day4>(input, skipped),
+        5 => solve_both::<day5::Day5>(input, skipped),
+        6 => solve_both::<day6::Day6>(input, skipped),
+        7 => solve_both::<day7::Day7>(input, skipped),
+        8 => solve_both::<day8::Day8>(input, skipped),
+        9 => solve_both::<day9::Day9>(input, skipped),
+        10 => solve_both::<day10::Day10>(input, skipped),
+        _ => Err(Error::Unsolved { day }),
+    }
+}
+
+/// Parse an input once, then solve both parts from it
+fn solve_both<S: Solution>(input: &str, skipped: &mut Skipped) -> Result<[String; 2], Error> {
+    let parsed = S::parse(input, skipped)?;
+    let part1 = S::part1(&parsed)?.to_string();
+    let part2 = S::part2(&parsed)?.to_string();
+
+    Ok([part1, part2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let input = include_str!("../../day04/example.txt");
+        let answers = run(4, input, &mut Skipped::new(true)).unwrap();
+        assert_eq!(answers, ["2", "4"]);
+
+        let input = include_str!("../../day10/example.txt");
+        let [_, crt] = run(10, input, &mut Skipped::new(true)).unwrap();
+        assert_eq!(crt.lines().count(), 6);
+        assert!(crt.starts_with("##..##..##"));
+
+        assert!(matches!(
+            run(7, "", &mut Skipped::new(true)),
+            Err(Error::Unsolved { day: 7 })
+        ));
+        assert!(matches!(
+            run(25, "", &mut Skipped::new(true)),
+            Err(Error::Unsolved { day: 25 })
+        ));
+    }
+
+    #[test]
+    fn test_run_lenient() {
+        let mut skipped = Skipped::new(false);
+        let answers = run(4, "2-8,3-7\n2-x,4-5\n5-7,7-9\n", &mut skipped).unwrap();
+
+        assert_eq!(answers, ["1", "2"]);
+        assert_eq!(skipped.errors().len(), 1);
+    }
+}